@@ -6,16 +6,15 @@ fn main() {
     let colour_theme = vec![
         247, 78, 222, 220, 214, 208, 202, 196, 162, 160, 126, 90, 88, 54, 53, 52,
     ];
-    let board = (0..16)
-        .map(|x| {
-            cell::Cell::new(
-                2_f64.powi(x + 1),
-                7,
-                *colour_theme.get(x as usize).unwrap() as u8,
-            )
-        })
-        .collect::<Vec<_>>();
-    let mut data = matrix::Matrix::new(4, board);
-    let display = MatrixDisplay::new(&format, &mut data);
+    // Each tile holds the exponent of its value
+    let board = matrix::Matrix::from_fn(4, 4, |r, c| (4 * r + c) as i32);
+    let value = number::NumberFormat::fixed(0).with_thousands(',');
+    let display = MatrixDisplay::styled(&format, &board, |_, _, &x| {
+        cell::Cell::new(
+            value.format(2_f64.powi(x + 1)),
+            7,
+            colour_theme[x as usize] as u8,
+        )
+    });
     display.print(&mut std::io::stdout(), &style::BordersStyle::Heavy);
 }
@@ -0,0 +1,26 @@
+extern crate matrix_display;
+use matrix_display::heatmap::{Colormap, Heatmap};
+use matrix_display::legend::{Legend, LegendPosition};
+use matrix_display::*;
+
+fn main() {
+    // Distances between points on a line, with an unknown distance
+    let points = [0.0_f64, 1.5, 4.0, 4.5, 9.0, 12.0];
+    let distances = matrix::Matrix::from_fn(points.len(), points.len(), |r, c| {
+        if (r, c) == (1, 4) {
+            f64::NAN
+        } else {
+            (points[r] - points[c]).abs()
+        }
+    });
+    let heatmap = Heatmap::new(Colormap::Viridis);
+    let value = number::NumberFormat::fixed(1).with_thousands(',');
+    let mut cells = heatmap.cells(&distances, |&x| value.format(x));
+    let format = Format::new(7, 1);
+    let mut display = MatrixDisplay::new(&format, &mut cells);
+    display.set_legend(
+        Legend::heatmap(&heatmap, &distances, value),
+        LegendPosition::Right,
+    );
+    display.print(&mut std::io::stdout(), &style::BordersStyle::Light);
+}
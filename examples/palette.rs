@@ -7,6 +7,7 @@ fn main() {
         .map(|x| cell::Cell::new(x, 0, x as u8))
         .collect::<Vec<_>>();
     let mut data = matrix::Matrix::new(8, board);
-    let display = MatrixDisplay::new(&format, &mut data);
+    let mut display = MatrixDisplay::new(&format, &mut data);
+    display.set_readable_foreground(4.5);
     display.print(&mut std::io::stdout(), &style::BordersStyle::Light);
 }
@@ -0,0 +1,188 @@
+//! Alternate background colors by row, by column, or in a checkerboard
+//!
+//! Bands tint every cell, or with `only_default_background` only the cells that keep the default
+//! background, so cells with a color of their own stand out.
+//! Conditional formatting rules apply after banding.
+
+use crate::cell::AnsiColor;
+
+#[cfg(test)]
+mod banding_tests {
+    use super::Banding;
+    use crate::cell::Cell;
+    use crate::matrix::Matrix;
+    use crate::screen::VirtualScreen;
+    use crate::style::BordersStyle;
+    use crate::{Format, MatrixDisplay};
+    use ansi_term::Colour::Fixed;
+    fn backgrounds(banding: Banding) -> Matrix<u8> {
+        Matrix::from_fn(4, 4, |r, c| banding.background(r, c))
+    }
+    #[test]
+    fn patterns() {
+        assert_eq!(
+            vec![1, 2, 1, 2],
+            backgrounds(Banding::rows(1, 2))
+                .col(3)
+                .cloned()
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![1, 2, 1, 2],
+            backgrounds(Banding::cols(1, 2)).row(3).to_vec()
+        );
+        assert_eq!(
+            Matrix::from([[1, 2, 1, 2], [2, 1, 2, 1], [1, 2, 1, 2], [2, 1, 2, 1]]),
+            backgrounds(Banding::checkerboard(1, 2))
+        );
+        assert_eq!(
+            vec![1, 1, 2, 2],
+            backgrounds(Banding::rows(1, 2).with_width(2))
+                .col(0)
+                .cloned()
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Matrix::from([[1, 1, 2, 2], [1, 1, 2, 2], [2, 2, 1, 1], [2, 2, 1, 1]]),
+            backgrounds(Banding::checkerboard(1, 2).with_width(2))
+        );
+    }
+    #[test]
+    fn every_background_is_tinted() {
+        let f = Format::new(1, 1);
+        let mut m = Matrix::from([[Cell::new('a', 7, 0), Cell::new('b', 7, 5)]]);
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.set_banding(Banding::cols(236, 238));
+        let mut s = VirtualScreen::new();
+        d.print(&mut s, &BordersStyle::None);
+        assert_eq!(Some(Fixed(236)), s.bg((1, 1)));
+        assert_eq!(Some(Fixed(238)), s.bg((2, 1)));
+    }
+    #[test]
+    fn only_default_backgrounds_are_tinted() {
+        let f = Format::new(1, 1);
+        let mut m = Matrix::from([
+            [Cell::new('a', 7, 0), Cell::new('b', 7, 5)],
+            [Cell::new('c', 7, 0), Cell::new('d', 7, 0)],
+        ]);
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.set_banding(Banding::rows(0, 236).only_default_background());
+        let mut s = VirtualScreen::new();
+        d.print(&mut s, &BordersStyle::None);
+        assert_eq!(vec!["ab", "cd"], s.lines());
+        assert_eq!(Some(Fixed(0)), s.bg((1, 1)));
+        assert_eq!(Some(Fixed(5)), s.bg((2, 1)));
+        assert_eq!(Some(Fixed(236)), s.bg((1, 2)));
+        assert_eq!(Some(Fixed(236)), s.bg((2, 2)));
+    }
+    #[test]
+    fn black_is_kept_as_color_16() {
+        let f = Format::new(1, 1);
+        let mut m = Matrix::from([[Cell::new('a', 7, 0), Cell::new('b', 7, 16)]]);
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.set_banding(Banding::rows(236, 238).only_default_background());
+        let mut s = VirtualScreen::new();
+        d.print(&mut s, &BordersStyle::None);
+        assert_eq!(Some(Fixed(236)), s.bg((1, 1)));
+        assert_eq!(Some(Fixed(16)), s.bg((2, 1)));
+    }
+}
+
+/// How bands alternate
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BandPattern {
+    /// Horizontal stripes
+    Rows,
+    /// Vertical stripes
+    Cols,
+    /// Alternating along both rows and columns, like a chess board
+    Checkerboard,
+}
+
+/// Alternating background colors for the cells of a `MatrixDisplay`
+///
+/// The first band, starting with the top left cell, gets the first color.
+///
+/// Every cell is tinted, unless `only_default_background` restricts banding to the cells with the
+/// default background, 0. Cells don't have a way to leave their background unset, so those cells
+/// then get tinted even when they explicitly asked for black: use 16, the black of the 256 colors
+/// cube, to keep it.
+///
+/// # Example: a chess board without computing its colors by hand
+/// ```
+/// use matrix_display::*;
+/// let format = Format::new(3, 1);
+/// let mut board = matrix::Matrix::filled(8, 8, cell::Cell::new(' ', 33, 0));
+/// let mut display = MatrixDisplay::new(&format, &mut board);
+/// display.set_banding(banding::Banding::checkerboard(0, 7));
+/// display.print(&mut std::io::stdout(), &style::BordersStyle::None);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Banding {
+    pub pattern: BandPattern,
+    pub colors: (u8, u8),
+    /// The number of rows or columns in each band
+    pub width: usize,
+    /// Only tint the cells with the default background
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub only_default: bool,
+}
+impl Banding {
+    /// Construct a banding with the given pattern, alternating two background colors every cell
+    pub fn new(pattern: BandPattern, first: u8, second: u8) -> Banding {
+        Banding {
+            pattern,
+            colors: (first, second),
+            width: 1,
+            only_default: false,
+        }
+    }
+    /// Alternate colors every row, also known as zebra striping
+    pub fn rows(first: u8, second: u8) -> Banding {
+        Banding::new(BandPattern::Rows, first, second)
+    }
+    /// Alternate colors every column
+    pub fn cols(first: u8, second: u8) -> Banding {
+        Banding::new(BandPattern::Cols, first, second)
+    }
+    /// Alternate colors every cell, along rows and columns
+    pub fn checkerboard(first: u8, second: u8) -> Banding {
+        Banding::new(BandPattern::Checkerboard, first, second)
+    }
+    /// Make each band `width` rows or columns wide, at least one
+    pub fn with_width(self, width: usize) -> Banding {
+        Banding {
+            width: std::cmp::max(1, width),
+            ..self
+        }
+    }
+    /// Only tint the cells with the default background, 0, leaving the others with their own color
+    pub fn only_default_background(self) -> Banding {
+        Banding {
+            only_default: true,
+            ..self
+        }
+    }
+    /// The background color of the cell at (row, col)
+    pub fn background(&self, row: usize, col: usize) -> u8 {
+        let band = match self.pattern {
+            BandPattern::Rows => row / self.width,
+            BandPattern::Cols => col / self.width,
+            BandPattern::Checkerboard => row / self.width + col / self.width,
+        };
+        if band % 2 == 0 {
+            self.colors.0
+        } else {
+            self.colors.1
+        }
+    }
+    /// Tint a color with the band's background, unless only default backgrounds are tinted and it has
+    /// a background of its own
+    pub(crate) fn tint(&self, color: &mut AnsiColor, (row, col): (usize, usize)) {
+        if !self.only_default || color.bg == AnsiColor::default().bg {
+            color.bg = self.background(row, col);
+        }
+    }
+}
@@ -2,9 +2,14 @@
 //!
 //! - The data type itself is generic. Anything that implements the ToString and the Clone traits can do
 //! - The foreground and background color are individually configurable for each cell
+//!
+//! Any other type can be displayed directly by implementing `RenderCell`,
+//! which supplies the text, colors and attributes of a cell.
 
 pub use self::cell::AnsiColor;
+pub use self::cell::Attributes;
 pub use self::cell::Cell;
+pub use self::cell::RenderCell;
 
 #[cfg(test)]
 mod cell_tests {
@@ -17,6 +22,34 @@ mod cell_tests {
         assert_eq!(c.color.bg, 24);
     }
     #[test]
+    fn cell_renders_its_value_and_color() {
+        use super::{Attributes, RenderCell};
+        let c = Cell::new(42, 1, 2);
+        assert_eq!("42", c.text());
+        assert_eq!(c.color, c.color());
+        assert_eq!(Attributes::default(), c.attributes());
+    }
+    #[test]
+    fn attributes_style() {
+        use super::Attributes;
+        let plain = ansi_term::Style::new();
+        assert_eq!(plain, Attributes::default().apply(plain));
+        let bold = Attributes {
+            bold: true,
+            underline: true,
+            ..Attributes::default()
+        };
+        assert_eq!(plain.bold().underline(), bold.apply(plain));
+    }
+    #[test]
+    fn readable_color() {
+        use super::AnsiColor;
+        let dark_on_dark = AnsiColor { fg: 0, bg: 17 };
+        assert_eq!(AnsiColor { fg: 231, bg: 17 }, dark_on_dark.readable(4.5));
+        let light_on_dark = AnsiColor { fg: 7, bg: 0 };
+        assert_eq!(light_on_dark, light_on_dark.readable(4.5));
+    }
+    #[test]
     fn clone_and_partial_eq() {
         let c = Cell::new('F', 42, 12);
         let d = c.clone();
@@ -24,13 +57,15 @@ mod cell_tests {
     }
 }
 
+#[allow(clippy::module_inception)]
 mod cell {
     /// A foreground and background color's ansi code
     ///
     /// # Example:
     /// `matrix_display::cell::AnsiColor{ fg: 7, bg: 0 }`
     /// represents a white on dark color
-    #[derive(Clone, Debug, PartialEq)]
+    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct AnsiColor {
         pub fg: u8,
         pub bg: u8,
@@ -40,6 +75,84 @@ mod cell {
             AnsiColor { fg: 7, bg: 0 }
         }
     }
+    impl AnsiColor {
+        /// The same colors, with a black or white foreground if needed to contrast with the
+        /// background by at least `threshold`
+        ///
+        /// See `palette::readable`.
+        pub fn readable(&self, threshold: f64) -> AnsiColor {
+            AnsiColor {
+                fg: crate::palette::readable(self.fg, self.bg, threshold),
+                bg: self.bg,
+            }
+        }
+    }
+
+    /// Text attributes of a rendered cell, all off by default
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct Attributes {
+        pub bold: bool,
+        pub dimmed: bool,
+        pub italic: bool,
+        pub underline: bool,
+        pub blink: bool,
+        pub reverse: bool,
+        pub strikethrough: bool,
+    }
+    impl Attributes {
+        /// Add these attributes to a style
+        pub fn apply(self, style: ansi_term::Style) -> ansi_term::Style {
+            ansi_term::Style {
+                is_bold: style.is_bold || self.bold,
+                is_dimmed: style.is_dimmed || self.dimmed,
+                is_italic: style.is_italic || self.italic,
+                is_underline: style.is_underline || self.underline,
+                is_blink: style.is_blink || self.blink,
+                is_reverse: style.is_reverse || self.reverse,
+                is_strikethrough: style.is_strikethrough || self.strikethrough,
+                ..style
+            }
+        }
+    }
+
+    /// Anything that can be rendered in a cell of a `GenericMatrixDisplay`
+    ///
+    /// # Example: a chess square rendered without wrapping it in a `Cell`
+    /// ```
+    /// use matrix_display::cell::{AnsiColor, Attributes, RenderCell};
+    /// #[derive(Clone)]
+    /// struct ChessSquare {
+    ///     piece: Option<char>,
+    ///     dark: bool,
+    ///     selected: bool,
+    /// }
+    /// impl RenderCell for ChessSquare {
+    ///     fn text(&self) -> String {
+    ///         self.piece.unwrap_or(' ').to_string()
+    ///     }
+    ///     fn color(&self) -> AnsiColor {
+    ///         AnsiColor { fg: 33, bg: if self.dark { 0 } else { 7 } }
+    ///     }
+    ///     fn attributes(&self) -> Attributes {
+    ///         Attributes { reverse: self.selected, ..Attributes::default() }
+    ///     }
+    /// }
+    /// let format = matrix_display::Format::new(3, 1);
+    /// let square = ChessSquare { piece: None, dark: false, selected: false };
+    /// let mut board = matrix_display::matrix::Matrix::filled(8, 8, square);
+    /// let display = matrix_display::GenericMatrixDisplay::new(&format, &mut board);
+    /// ```
+    pub trait RenderCell {
+        /// The text printed in the cell, centered
+        fn text(&self) -> String;
+        /// The foreground and background colors of the cell
+        fn color(&self) -> AnsiColor;
+        /// The text attributes of the cell, none by default
+        fn attributes(&self) -> Attributes {
+            Attributes::default()
+        }
+    }
 
     /// A Matrix Cell that owns some data, a background color and a foreground color
     ///
@@ -68,7 +181,8 @@ mod cell {
     ///         })
     ///     .collect::<Vec<_>>();
     /// ```
-    #[derive(Clone, Debug, PartialEq)]
+    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Cell<T>
     where
         T: Clone,
@@ -86,8 +200,20 @@ mod cell {
         pub fn new(val: T, fg: u8, bg: u8) -> Cell<T> {
             Cell {
                 value: val,
-                color: AnsiColor { fg: fg, bg: bg },
+                color: AnsiColor { fg, bg },
             }
         }
     }
+    impl<T> RenderCell for Cell<T>
+    where
+        T: Clone,
+        T: ToString,
+    {
+        fn text(&self) -> String {
+            self.value.to_string()
+        }
+        fn color(&self) -> AnsiColor {
+            self.color.clone()
+        }
+    }
 }
@@ -0,0 +1,327 @@
+//! Color numeric matrices by value
+//!
+//! A `Heatmap` maps each value of a matrix to a position between 0 and 1 according to its `Scale`
+//! and range, then to a background color through a `Colormap`.
+//! Colormaps are defined in RGB and rendered with the closest colors of the 256 colour palette.
+//! NaN values get a color of their own.
+
+use crate::cell::Cell;
+use crate::matrix::Matrix;
+use crate::number::ToF64;
+use crate::palette;
+
+#[cfg(test)]
+mod heatmap_tests {
+    use super::{Colormap, Heatmap, Scale};
+    use crate::matrix::Matrix;
+    use crate::palette;
+    #[test]
+    fn colormap_ends() {
+        assert_eq!((0x44, 0x01, 0x54), Colormap::Viridis.rgb(0.0));
+        assert_eq!((0xfd, 0xe7, 0x25), Colormap::Viridis.rgb(1.0));
+        assert_eq!((0, 0, 0), Colormap::Grayscale.rgb(-1.0));
+        assert_eq!((255, 255, 255), Colormap::Grayscale.rgb(2.0));
+        assert_eq!((128, 128, 128), Colormap::Grayscale.rgb(0.5));
+        assert_eq!(
+            palette::nearest((0xf7, 0xf7, 0xf7)),
+            Colormap::RedBlue.color(0.5)
+        );
+    }
+    #[test]
+    fn linear_scale() {
+        let m = Matrix::from([[0.0, 5.0, 10.0]]);
+        let h = Heatmap::new(Colormap::Grayscale);
+        assert_eq!(Some((0.0, 10.0)), h.range(&m));
+        let gray = Colormap::Grayscale;
+        assert_eq!(
+            Matrix::from([[gray.color(0.0), gray.color(0.5), gray.color(1.0)]]),
+            h.colors(&m)
+        );
+        let fixed = h.with_range(0.0, 20.0);
+        assert_eq!(
+            Matrix::from([[gray.color(0.0), gray.color(0.25), gray.color(0.5)]]),
+            fixed.colors(&m)
+        );
+    }
+    #[test]
+    fn log_scale() {
+        let m = Matrix::from([[1.0, 10.0, 100.0, -1.0]]);
+        let h = Heatmap::new(Colormap::Grayscale).with_scale(Scale::Log);
+        assert_eq!(Some((1.0, 100.0)), h.range(&m));
+        let gray = Colormap::Grayscale;
+        assert_eq!(
+            Matrix::from([[
+                gray.color(0.0),
+                gray.color(0.5),
+                gray.color(1.0),
+                gray.color(0.0)
+            ]]),
+            h.colors(&m)
+        );
+    }
+    #[test]
+    fn quantile_scale() {
+        let m = Matrix::from([[1.0, 2.0, 1000.0, 2.0, 5.0]]);
+        let h = Heatmap::new(Colormap::Grayscale).with_scale(Scale::Quantile);
+        let gray = Colormap::Grayscale;
+        assert_eq!(
+            Matrix::from([[
+                gray.color(0.0),
+                gray.color(0.375),
+                gray.color(1.0),
+                gray.color(0.375),
+                gray.color(0.75)
+            ]]),
+            h.colors(&m)
+        );
+    }
+    #[test]
+    fn nan_values() {
+        let m = Matrix::from([[f64::NAN, 1.0, 2.0]]);
+        assert_eq!(8, Heatmap::new(Colormap::Magma).colors(&m)[(0, 0)]);
+        let h = Heatmap::new(Colormap::Magma).with_nan_color(9);
+        assert_eq!(Some((1.0, 2.0)), h.range(&m));
+        assert_eq!(9, h.colors(&m)[(0, 0)]);
+        assert_eq!(None, h.range(&Matrix::from([[f64::NAN]])));
+        assert_eq!(Matrix::from([[9]]), h.colors(&Matrix::from([[f64::NAN]])));
+    }
+    #[test]
+    fn wide_integers() {
+        let m = Matrix::from([[0_usize, 10], [5, 20]]);
+        let h = Heatmap::new(Colormap::Grayscale);
+        assert_eq!(Some((0.0, 20.0)), h.range(&m));
+        let m = Matrix::from([[i64::MIN, 0, i64::MAX]]);
+        assert_eq!(h.colormap.color(0.5), h.colors(&m)[(0, 1)]);
+    }
+    #[test]
+    fn cells() {
+        let m = Matrix::from([[1, 2]]);
+        let cells = Heatmap::new(Colormap::Viridis).cells(&m, |x| x.to_string());
+        assert_eq!("2", cells[(0, 1)].value);
+        assert_eq!(Colormap::Viridis.color(1.0), cells[(0, 1)].color.bg);
+    }
+}
+
+/// A gradient of colors, from the lowest values to the highest
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Colormap {
+    /// Perceptually uniform, from dark purple to yellow
+    Viridis,
+    /// Perceptually uniform, from black to pale yellow through purple
+    Magma,
+    /// From black to white
+    Grayscale,
+    /// Diverging, from dark red to dark blue through white, for values on either side of a midpoint
+    RedBlue,
+}
+
+const VIRIDIS: [(u8, u8, u8); 10] = [
+    (0x44, 0x01, 0x54),
+    (0x48, 0x28, 0x78),
+    (0x3e, 0x49, 0x89),
+    (0x31, 0x68, 0x8e),
+    (0x26, 0x82, 0x8e),
+    (0x1f, 0x9e, 0x89),
+    (0x35, 0xb7, 0x79),
+    (0x6e, 0xce, 0x58),
+    (0xb5, 0xde, 0x2b),
+    (0xfd, 0xe7, 0x25),
+];
+const MAGMA: [(u8, u8, u8); 10] = [
+    (0x00, 0x00, 0x04),
+    (0x18, 0x0f, 0x3d),
+    (0x44, 0x0f, 0x76),
+    (0x72, 0x1f, 0x81),
+    (0x9e, 0x2f, 0x7f),
+    (0xcd, 0x40, 0x71),
+    (0xf1, 0x60, 0x5d),
+    (0xfd, 0x96, 0x68),
+    (0xfe, 0xca, 0x8d),
+    (0xfc, 0xfd, 0xbf),
+];
+const GRAYSCALE: [(u8, u8, u8); 2] = [(0, 0, 0), (255, 255, 255)];
+const RED_BLUE: [(u8, u8, u8); 11] = [
+    (0x67, 0x00, 0x1f),
+    (0xb2, 0x18, 0x2b),
+    (0xd6, 0x60, 0x4d),
+    (0xf4, 0xa5, 0x82),
+    (0xfd, 0xdb, 0xc7),
+    (0xf7, 0xf7, 0xf7),
+    (0xd1, 0xe5, 0xf0),
+    (0x92, 0xc5, 0xde),
+    (0x43, 0x93, 0xc3),
+    (0x21, 0x66, 0xac),
+    (0x05, 0x30, 0x61),
+];
+
+impl Colormap {
+    /// The RGB color at position `t`, clamped between 0 and 1
+    pub fn rgb(self, t: f64) -> (u8, u8, u8) {
+        let stops: &[(u8, u8, u8)] = match self {
+            Colormap::Viridis => &VIRIDIS,
+            Colormap::Magma => &MAGMA,
+            Colormap::Grayscale => &GRAYSCALE,
+            Colormap::RedBlue => &RED_BLUE,
+        };
+        let position = t.clamp(0.0, 1.0) * (stops.len() - 1) as f64;
+        let i = std::cmp::min(position.floor() as usize, stops.len() - 2);
+        let frac = position - i as f64;
+        let (from, to) = (stops[i], stops[i + 1]);
+        let lerp =
+            |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * frac).round() as u8;
+        (lerp(from.0, to.0), lerp(from.1, to.1), lerp(from.2, to.2))
+    }
+    /// The ansi code of the palette color closest to the color at position `t`
+    pub fn color(self, t: f64) -> u8 {
+        palette::nearest(self.rgb(t))
+    }
+}
+
+/// How values are spread along a colormap
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Scale {
+    /// Proportionally to their distance to the minimum
+    Linear,
+    /// Proportionally to their logarithm; values that aren't positive get the lowest color
+    Log,
+    /// By rank, so that each color is used about as often; the range is ignored
+    Quantile,
+}
+
+/// Maps the values of a matrix to background colors
+///
+/// # Example: coloring a correlation matrix
+/// ```
+/// use matrix_display::*;
+/// use matrix_display::heatmap::{Colormap, Heatmap};
+/// let correlations = matrix::Matrix::from([[1.0, 0.2, -0.7], [0.2, 1.0, 0.4], [-0.7, 0.4, 1.0]]);
+/// let heatmap = Heatmap::new(Colormap::RedBlue).with_range(-1.0, 1.0);
+/// let value = number::NumberFormat::fixed(1).with_sign(number::Sign::Space);
+/// let mut cells = heatmap.cells(&correlations, |&x| value.format(x));
+/// let format = Format::new(6, 1);
+/// let display = MatrixDisplay::new(&format, &mut cells);
+/// display.print(&mut std::io::stdout(), &style::BordersStyle::Light);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Heatmap {
+    pub colormap: Colormap,
+    pub scale: Scale,
+    /// The values mapped to both ends of the colormap, or None to use the smallest and largest values
+    pub range: Option<(f64, f64)>,
+    /// The background of NaN values, gray by default
+    pub nan_color: u8,
+}
+impl Heatmap {
+    /// Construct a heatmap with a linear scale, an automatic range, and a gray background for NaN values
+    ///
+    /// Gray (color 8) sets NaN values apart from the default background of the cells around the heatmap.
+    pub fn new(colormap: Colormap) -> Heatmap {
+        Heatmap {
+            colormap,
+            scale: Scale::Linear,
+            range: None,
+            nan_color: 8,
+        }
+    }
+    /// Spread values along the colormap according to `scale`
+    pub fn with_scale(self, scale: Scale) -> Heatmap {
+        Heatmap { scale, ..self }
+    }
+    /// Map `min` and `max` to the ends of the colormap, clamping the values outside of them
+    pub fn with_range(self, min: f64, max: f64) -> Heatmap {
+        Heatmap {
+            range: Some((min, max)),
+            ..self
+        }
+    }
+    /// Give NaN values the `bg` background
+    pub fn with_nan_color(self, bg: u8) -> Heatmap {
+        Heatmap {
+            nan_color: bg,
+            ..self
+        }
+    }
+    /// The values mapped to both ends of the colormap for this matrix
+    ///
+    /// This is the fixed range if there is one, or else the smallest and largest finite values
+    /// (positive values only with a log scale). None if the matrix has no such value.
+    pub fn range<T>(&self, m: &Matrix<T>) -> Option<(f64, f64)>
+    where
+        T: Clone + ToF64,
+    {
+        if self.range.is_some() {
+            return self.range;
+        }
+        let log = self.scale == Scale::Log;
+        m.iter()
+            .map(|x| x.to_f64())
+            .filter(|x: &f64| x.is_finite() && (!log || *x > 0.0))
+            .fold(None, |range, x| match range {
+                None => Some((x, x)),
+                Some((min, max)) => Some((min.min(x), max.max(x))),
+            })
+    }
+    /// The background color of each value
+    pub fn colors<T>(&self, m: &Matrix<T>) -> Matrix<u8>
+    where
+        T: Clone + ToF64,
+    {
+        let range = self.range(m);
+        let mut sorted = m
+            .iter()
+            .map(|x| x.to_f64())
+            .filter(|x: &f64| !x.is_nan())
+            .collect::<Vec<_>>();
+        if self.scale == Scale::Quantile {
+            sorted.sort_by(|a, b| a.partial_cmp(b).expect("NaN values are filtered out"));
+        }
+        m.map(|x| {
+            let x = x.to_f64();
+            if x.is_nan() {
+                return self.nan_color;
+            }
+            // Without a range, every value is infinite and the ends of the colormap still apply
+            let (min, max) = range.unwrap_or((0.0, 0.0));
+            self.colormap.color(self.position(x, min, max, &sorted))
+        })
+    }
+    /// Cells holding the text of each value on its background color
+    pub fn cells<T, F>(&self, m: &Matrix<T>, text: F) -> Matrix<Cell<String>>
+    where
+        T: Clone + ToF64,
+        F: Fn(&T) -> String,
+    {
+        let colors = self.colors(m);
+        m.zip_with(&colors, |x, &bg| Cell::new(text(x), 7, bg))
+    }
+    /// The position of a value along the colormap, between 0 and 1
+    fn position(&self, x: f64, min: f64, max: f64, sorted: &[f64]) -> f64 {
+        let ratio = |x: f64, min: f64, max: f64| {
+            if max > min {
+                ((x - min) / (max - min)).clamp(0.0, 1.0)
+            } else {
+                0.5
+            }
+        };
+        match self.scale {
+            Scale::Linear => ratio(x, min, max),
+            Scale::Log => {
+                let ln = |v: f64| v.max(f64::MIN_POSITIVE).ln();
+                ratio(ln(x), ln(min), ln(max))
+            }
+            Scale::Quantile => {
+                if sorted.len() < 2 {
+                    return 0.5;
+                }
+                // Equal values share the average of their ranks
+                let below = sorted.partition_point(|&v| v < x);
+                let up_to = sorted.partition_point(|&v| v <= x);
+                let rank = (below + up_to.saturating_sub(1)) as f64 / 2.0;
+                rank / (sorted.len() - 1) as f64
+            }
+        }
+    }
+}
@@ -0,0 +1,309 @@
+//! A key to the colors of a matrix, drawn beside or below it
+//!
+//! - A gradient shows a colormap between tick labels for its minimum and maximum values:
+//!   a vertical bar beside the matrix, or a horizontal one below it
+//! - A discrete key lists colors next to the category they stand for
+//!
+//! Legends are framed with the same `BordersStyle` as the matrix, and sized after it.
+
+use crate::cell::AnsiColor;
+use crate::heatmap::{Colormap, Heatmap};
+use crate::matrix::Matrix;
+use crate::number::{NumberFormat, ToF64};
+use crate::style::BordersStyle;
+use crate::{border_width, color_style, line_width, plain_style, Line};
+use unicode_width::UnicodeWidthStr;
+
+#[cfg(test)]
+mod legend_tests {
+    use super::{Legend, LegendPosition};
+    use crate::cell::Cell;
+    use crate::heatmap::{Colormap, Heatmap};
+    use crate::matrix::Matrix;
+    use crate::number::NumberFormat;
+    use crate::screen::VirtualScreen;
+    use crate::style::BordersStyle;
+    use crate::{Format, MatrixDisplay};
+    use ansi_term::Colour::Fixed;
+    fn screen(
+        m: &mut Matrix<Cell<char>>,
+        legend: Legend,
+        position: LegendPosition,
+        borders: &BordersStyle,
+    ) -> VirtualScreen {
+        let f = Format::new(3, 1);
+        let mut d = MatrixDisplay::new(&f, m);
+        d.set_legend(legend, position);
+        let mut s = VirtualScreen::new();
+        d.print(&mut s, borders);
+        s
+    }
+    fn grid() -> Matrix<Cell<char>> {
+        Matrix::from_fn(2, 3, |_, _| Cell::new('x', 7, 0))
+    }
+    #[test]
+    fn gradient_below() {
+        let legend = Legend::gradient(Colormap::Grayscale, 0.0, 10.0, NumberFormat::fixed(1));
+        let s = screen(
+            &mut grid(),
+            legend,
+            LegendPosition::Below,
+            &BordersStyle::Light,
+        );
+        assert_eq!(
+            vec![
+                "┌───┬───┬───┐",
+                "│ x │ x │ x │",
+                "├───┼───┼───┤",
+                "│ x │ x │ x │",
+                "└───┴───┴───┘",
+                "┌───────────┐",
+                "│           │",
+                "└───────────┘",
+                "0.0      10.0",
+            ],
+            s.lines()
+        );
+        assert_eq!(Some(Fixed(Colormap::Grayscale.color(0.0))), s.bg((2, 7)));
+        assert_eq!(Some(Fixed(Colormap::Grayscale.color(1.0))), s.bg((12, 7)));
+    }
+    #[test]
+    fn gradient_beside() {
+        let legend = Legend::gradient(Colormap::Grayscale, 0.0, 10.0, NumberFormat::fixed(0));
+        let s = screen(
+            &mut grid(),
+            legend,
+            LegendPosition::Right,
+            &BordersStyle::None,
+        );
+        assert_eq!(vec![" x  x  x     10", " x  x  x     0"], s.lines());
+        assert_eq!(Some(Fixed(Colormap::Grayscale.color(1.0))), s.bg((11, 1)));
+        assert_eq!(Some(Fixed(Colormap::Grayscale.color(0.0))), s.bg((11, 2)));
+    }
+    #[test]
+    fn key_beside_a_short_matrix() {
+        let legend = Legend::key(
+            ["low", "mid", "high", "peak"]
+                .iter()
+                .zip(1..)
+                .map(|(label, bg)| (label.to_string(), bg))
+                .collect(),
+        );
+        let mut m = Matrix::from_fn(2, 2, |_, _| Cell::new('x', 7, 0));
+        let s = screen(&mut m, legend, LegendPosition::Right, &BordersStyle::Retro);
+        assert_eq!(
+            vec![
+                "+---+---+ +-------+",
+                "| x | x | |   low |",
+                "+---+---+ |   mid |",
+                "| x | x | |   high|",
+                "+---+---+ |   peak|",
+                "          +-------+",
+            ],
+            s.lines()
+        );
+        assert_eq!(Some(Fixed(2)), s.bg((12, 3)));
+    }
+    #[test]
+    fn heatmap_legend_uses_its_range() {
+        let m = Matrix::from([[1.0, 4.0]]);
+        let heatmap = Heatmap::new(Colormap::Magma);
+        assert_eq!(
+            Legend::gradient(Colormap::Magma, 1.0, 4.0, NumberFormat::fixed(2)),
+            Legend::heatmap(&heatmap, &m, NumberFormat::fixed(2))
+        );
+    }
+}
+
+/// Where a legend is drawn, relative to the matrix
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LegendPosition {
+    /// On the right of the matrix, from its top line
+    Right,
+    /// Under the matrix, from its left edge
+    Below,
+}
+
+/// A key to the colors of a matrix
+///
+/// # Example: a heatmap with its scale bar
+/// ```
+/// use matrix_display::*;
+/// use matrix_display::heatmap::{Colormap, Heatmap};
+/// use matrix_display::legend::{Legend, LegendPosition};
+/// let data = matrix::Matrix::from_fn(4, 4, |r, c| (r * c) as f64);
+/// let heatmap = Heatmap::new(Colormap::Viridis);
+/// let mut cells = heatmap.cells(&data, |x| x.to_string());
+/// let format = Format::new(5, 1);
+/// let mut display = MatrixDisplay::new(&format, &mut cells);
+/// display.set_legend(
+///     Legend::heatmap(&heatmap, &data, number::NumberFormat::fixed(0)),
+///     LegendPosition::Right,
+/// );
+/// display.print(&mut std::io::stdout(), &style::BordersStyle::Light);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum Legend {
+    /// A colormap between tick labels for the values at its ends
+    Gradient {
+        colormap: Colormap,
+        min: f64,
+        max: f64,
+        format: NumberFormat,
+    },
+    /// Labels, each next to the background color it stands for
+    Key(Vec<(String, u8)>),
+}
+impl Legend {
+    /// Construct a gradient legend from `min` to `max`, with tick labels written in `format`
+    pub fn gradient(colormap: Colormap, min: f64, max: f64, format: NumberFormat) -> Legend {
+        Legend::Gradient {
+            colormap,
+            min,
+            max,
+            format,
+        }
+    }
+    /// Construct the gradient legend of a heatmap of a matrix, over the heatmap's range for that matrix
+    pub fn heatmap<T>(heatmap: &Heatmap, m: &Matrix<T>, format: NumberFormat) -> Legend
+    where
+        T: Clone + ToF64,
+    {
+        let (min, max) = heatmap.range(m).unwrap_or((f64::NAN, f64::NAN));
+        Legend::gradient(heatmap.colormap, min, max, format)
+    }
+    /// Construct a discrete key from (label, background color) pairs
+    pub fn key(entries: Vec<(String, u8)>) -> Legend {
+        Legend::Key(entries)
+    }
+    /// Lay the legend out next to the rendered lines of a matrix
+    pub(crate) fn attach(
+        &self,
+        mut grid: Vec<Line>,
+        borders: &BordersStyle,
+        position: LegendPosition,
+    ) -> Vec<Line> {
+        let b = border_width(borders);
+        let grid_width = grid.iter().map(line_width).max().unwrap_or(0);
+        let legend = match position {
+            LegendPosition::Right => {
+                self.render(borders, position, grid.len().saturating_sub(2 * b))
+            }
+            LegendPosition::Below => {
+                self.render(borders, position, grid_width.saturating_sub(2 * b))
+            }
+        };
+        match position {
+            LegendPosition::Below => {
+                grid.extend(legend);
+                grid
+            }
+            LegendPosition::Right => {
+                let n_lines = std::cmp::max(grid.len(), legend.len());
+                grid.resize(n_lines, Vec::new());
+                grid.into_iter()
+                    .zip(legend.into_iter().map(Some).chain(std::iter::repeat(None)))
+                    .map(|(mut line, legend_line)| {
+                        if let Some(legend_line) = legend_line {
+                            let gap = grid_width - line_width(&line) + 1;
+                            line.push(plain_style().paint(" ".repeat(gap)));
+                            line.extend(legend_line);
+                        }
+                        line
+                    })
+                    .collect::<Vec<_>>()
+            }
+        }
+    }
+    /// Render the legend, with a gradient `length` characters long inside its borders
+    fn render(&self, borders: &BordersStyle, position: LegendPosition, length: usize) -> Vec<Line> {
+        let b = border_width(borders);
+        let length = std::cmp::max(2, length);
+        match *self {
+            Legend::Gradient {
+                colormap,
+                min,
+                max,
+                format,
+            } => {
+                let swatch = |i: usize| {
+                    let t = i as f64 / (length - 1) as f64;
+                    color_style(&AnsiColor {
+                        fg: 7,
+                        bg: colormap.color(t),
+                    })
+                };
+                let (min, max) = (format.format(min), format.format(max));
+                match position {
+                    LegendPosition::Below => {
+                        let bar = (0..length)
+                            .map(|i| swatch(i).paint(" "))
+                            .collect::<Vec<_>>();
+                        let mut lines = framed(vec![bar], length, borders);
+                        let gap = (length + 2 * b).saturating_sub(min.width() + max.width());
+                        let ticks = format!("{}{}{}", min, " ".repeat(std::cmp::max(1, gap)), max);
+                        lines.push(vec![plain_style().paint(ticks)]);
+                        lines
+                    }
+                    LegendPosition::Right => {
+                        // The highest values are at the top
+                        let bar = (0..length)
+                            .map(|i| vec![swatch(length - 1 - i).paint("  ")])
+                            .collect::<Vec<_>>();
+                        let mut lines = framed(bar, 2, borders);
+                        lines[b].push(plain_style().paint(format!(" {}", max)));
+                        lines[b + length - 1].push(plain_style().paint(format!(" {}", min)));
+                        lines
+                    }
+                }
+            }
+            Legend::Key(ref entries) => {
+                let label_width = entries
+                    .iter()
+                    .map(|(label, _)| label.width())
+                    .max()
+                    .unwrap_or(0);
+                let rows = entries
+                    .iter()
+                    .map(|(label, bg)| {
+                        let padding = " ".repeat(label_width - label.width());
+                        vec![
+                            color_style(&AnsiColor { fg: 7, bg: *bg }).paint("  "),
+                            plain_style().paint(format!(" {}{}", label, padding)),
+                        ]
+                    })
+                    .collect::<Vec<_>>();
+                framed(rows, 3 + label_width, borders)
+            }
+        }
+    }
+}
+
+/// Surround lines that are `width` characters wide with borders
+fn framed(lines: Vec<Line>, width: usize, borders: &BordersStyle) -> Vec<Line> {
+    if border_width(borders) == 0 {
+        return lines;
+    }
+    let plain = plain_style();
+    let horizontal = borders.horizontal_border().to_string().repeat(width);
+    let mut framed = vec![vec![plain.paint(format!(
+        "{}{}{}",
+        borders.top_left_corner(),
+        horizontal,
+        borders.top_right_corner()
+    ))]];
+    for line in lines {
+        let mut bordered = vec![plain.paint(borders.vertical_border().to_string())];
+        bordered.extend(line);
+        bordered.push(plain.paint(borders.vertical_border().to_string()));
+        framed.push(bordered);
+    }
+    framed.push(vec![plain.paint(format!(
+        "{}{}{}",
+        borders.bottom_left_corner(),
+        horizontal,
+        borders.bottom_right_corner()
+    ))]);
+    framed
+}
@@ -2,6 +2,21 @@
 //!
 //! - Supports 256 terminal colours using AnsiTerm
 //! - Multiple unicode box character sets supported (plain, retro, thin, rounded, thick, double)
+//! - Incremental redraw of only the cells that changed since the previous frame
+//! - Viewports with scroll indicators for matrices larger than the terminal
+//! - Frozen header rows and columns that stay in place while the rest scrolls
+//! - Fit-to-terminal layouts that follow terminal resizes
+//! - Pagination of large matrices into labelled, printable pages
+//! - Any type can be displayed by implementing `RenderCell`, with bold, italic, underline... attributes, through `GenericMatrixDisplay`
+//! - Heatmaps coloring numeric matrices through viridis, magma, grayscale or red-blue colormaps
+//! - Foregrounds automatically chosen to stay readable on any background
+//! - Zebra striping, column banding and checkerboards
+//! - Conditional formatting rules restyling cells by value, rank, position or neighbours
+//! - Legends showing a colormap's scale or a key of colors beside or below the matrix
+//! - Number formatting (precision, significant digits, scientific, SI suffixes, percentages, thousands separators) with aligned columns
+//! - Optional `serde` feature to save and load matrices, cells, formats and border styles
+//! - Optional `ndarray` feature to convert `Array2` to and from `Matrix` and to display array views directly
+//! - Optional `nalgebra` feature to convert `DMatrix` and `SMatrix` to and from `Matrix`
 //!
 //! #Example use cases:
 //! [chess-rs: a chess game](https://github.com/pierrechevalier83/chess-rs)
@@ -44,23 +59,45 @@
 //! }
 //! ```
 
+// Unit tests sit at the top of each module, before the code they test
+#![allow(clippy::items_after_test_module)]
+
+pub mod banding;
 pub mod cell;
+pub mod heatmap;
+pub mod legend;
 pub mod matrix;
+pub mod number;
 mod pad;
+pub mod pagination;
+pub mod palette;
+pub mod redraw;
+pub mod rules;
+pub mod screen;
 pub mod style;
+pub mod terminal;
+pub mod viewport;
 
+use crate::banding::Banding;
 use crate::cell::AnsiColor;
 use crate::cell::Cell;
+use crate::cell::RenderCell;
+use crate::legend::{Legend, LegendPosition};
 use crate::matrix::position::Position;
-use crate::matrix::Matrix;
+use crate::matrix::{Matrix, MatrixView, MatrixViewMut};
 use crate::pad::horizontal_pad;
 use crate::pad::Pad;
+use crate::rules::Rules;
 use crate::style::BordersStyle;
+use crate::viewport::{Viewport, ViewportSize};
 
 extern crate ansi_term;
 
 use ansi_term::Colour::Fixed;
+use ansi_term::Style;
 use std::io::Write;
+use std::ops::Range;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 #[cfg(test)]
 mod format_tests {
@@ -77,6 +114,24 @@ mod format_tests {
         assert_eq!(f.cell_w, 1);
         assert_eq!(f.cell_h, 1);
     }
+    #[test]
+    fn fit() {
+        use crate::style::BordersStyle;
+        // 4 * (19 + 1) + 1 = 81 > 80
+        let f = Format::fit(3, 4, &BordersStyle::Light, (80, 24));
+        assert_eq!(f.cell_w, 18);
+        assert_eq!(f.cell_h, 6);
+        let f = Format::fit(3, 4, &BordersStyle::None, (80, 24));
+        assert_eq!(f.cell_w, 20);
+        assert_eq!(f.cell_h, 8);
+    }
+    #[test]
+    fn fit_never_goes_below_one_character() {
+        use crate::style::BordersStyle;
+        let f = Format::fit(100, 100, &BordersStyle::Light, (80, 24));
+        assert_eq!(f.cell_w, 1);
+        assert_eq!(f.cell_h, 1);
+    }
 }
 
 /// Format of a cell in a matrix
@@ -86,6 +141,7 @@ mod format_tests {
 ///
 /// Example:
 /// `let format = matrix_display::Format::new(7,3)`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Format {
     pub cell_w: usize,
     pub cell_h: usize,
@@ -97,6 +153,26 @@ impl Format {
             cell_h: h,
         }
     }
+    /// The largest format for which a matrix of n_rows by n_cols, borders included,
+    /// fits within (width, height) characters
+    ///
+    /// Cells are at least one character wide and high, so a large matrix may still overflow:
+    /// use a viewport to scroll through it.
+    pub fn fit(
+        n_rows: usize,
+        n_cols: usize,
+        borders: &BordersStyle,
+        (width, height): (usize, usize),
+    ) -> Format {
+        let b = border_width(borders);
+        let fit = |chars: usize, n_cells: usize| {
+            std::cmp::max(
+                1,
+                (chars.saturating_sub(b) / std::cmp::max(n_cells, 1)).saturating_sub(b),
+            )
+        };
+        Format::new(fit(width, n_cols), fit(height, n_rows))
+    }
 }
 impl Default for Format {
     fn default() -> Format {
@@ -137,126 +213,675 @@ mod matrix_display_tests {
     }
 }
 
+#[cfg(test)]
+mod ragged_display_tests {
+    use super::Format;
+    use super::MatrixDisplay;
+    use crate::cell::Cell;
+    use crate::matrix::Matrix;
+    use crate::screen::fixtures::screen;
+    use crate::style::BordersStyle;
+    use crate::viewport::Viewport;
+    fn seven_letters() -> Matrix<Cell<char>> {
+        let cells = "abcdefg".chars().map(|x| Cell::new(x, 7, 0)).collect();
+        Matrix::ragged(3, cells, Cell::new(' ', 7, 0)).unwrap()
+    }
+    #[test]
+    fn bottom_border_follows_the_last_row() {
+        let f = Format::new(1, 1);
+        let mut m = seven_letters();
+        let d = MatrixDisplay::new(&f, &mut m);
+        assert_eq!(
+            vec![
+                "┌─┬─┬─┐",
+                "│a│b│c│",
+                "├─┼─┼─┤",
+                "│d│e│f│",
+                "├─┼─┴─┘",
+                "│g│",
+                "└─┘"
+            ],
+            screen(&d, &BordersStyle::Light)
+        );
+        assert_eq!(vec!["abc", "def", "g"], screen(&d, &BordersStyle::None));
+        let mut m = Matrix::ragged(3, vec![Cell::new('a', 7, 0)], Cell::new(' ', 7, 0)).unwrap();
+        let d = MatrixDisplay::new(&f, &mut m);
+        assert_eq!(vec!["┌─┐", "│a│", "└─┘"], screen(&d, &BordersStyle::Light));
+    }
+    #[test]
+    fn styled_data_keeps_its_ragged_row() {
+        let f = Format::new(1, 1);
+        let m = Matrix::ragged(2, vec!['a', 'b', 'c'], ' ').unwrap();
+        let d = MatrixDisplay::styled(&f, &m, |_, _, &x| Cell::new(x, 7, 0));
+        assert_eq!(
+            vec!["╔═╦═╗", "║a║b║", "╠═╬═╝", "║c║", "╚═╝"],
+            screen(&d, &BordersStyle::Double)
+        );
+    }
+    #[test]
+    fn viewport_past_the_last_row_closes_the_row_above() {
+        let f = Format::new(1, 1);
+        let mut m = seven_letters();
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.set_viewport(Viewport::cells(3, 2).at(0, 1));
+        assert_eq!(
+            vec!["┌─┬─┐", "│b│c│", "├─┼─┤", "│e│f│", "└─┴─┘"],
+            screen(&d, &BordersStyle::Light)
+        );
+    }
+}
+
+#[cfg(test)]
+mod viewport_display_tests {
+    use super::Format;
+    use super::{GenericMatrixDisplay, MatrixDisplay};
+    use crate::cell::Cell;
+    use crate::matrix::Matrix;
+    use crate::screen::fixtures::{letters, screen};
+    use crate::screen::VirtualScreen;
+    use crate::style::BordersStyle;
+    use crate::viewport::Viewport;
+    #[test]
+    fn views_render_a_region() {
+        let f = Format::new(1, 1);
+        let mut m = letters();
+        {
+            let d = MatrixDisplay::from_view(&f, m.view(1.., 1..3));
+            assert_eq!(vec!["fg", "jk"], screen(&d, &BordersStyle::None));
+            assert_eq!((1, 0), d.coordinates_at_cursor_position((2, 1)));
+        }
+        {
+            let mut d = MatrixDisplay::from_view_mut(&f, m.view_mut(..2, 2..));
+            d.cell_at_cursor_position((2, 2)).value = 'z';
+            assert_eq!(vec!["cd", "gz"], screen(&d, &BordersStyle::None));
+        }
+        assert_eq!('z', m[(1, 3)].value);
+    }
+    #[test]
+    fn read_only_cells_are_hit_tested_but_not_modified() {
+        let f = Format::new(1, 1);
+        let m = letters();
+        let mut d = MatrixDisplay::from_view(&f, m.view(1.., 1..3));
+        assert_eq!((1, 1), d.coordinates_at_cursor_position((2, 2)));
+        assert!(d.try_cell_at_cursor_position((2, 2)).is_none());
+        let mut d = MatrixDisplay::styled(&f, &m, |_, _, cell| cell.clone());
+        assert_eq!((3, 2), d.coordinates_at_cursor_position((4, 3)));
+        assert!(d.try_cell_at_cursor_position((4, 3)).is_none());
+    }
+    #[test]
+    #[should_panic(expected = "can't be modified")]
+    fn read_only_cells_are_not_given_out() {
+        let f = Format::new(1, 1);
+        let m = letters();
+        let mut d = MatrixDisplay::from_view(&f, m.view(.., ..));
+        d.cell_at_cursor_position((1, 1));
+    }
+    #[test]
+    fn styled_data_is_rendered() {
+        let f = Format::new(1, 1);
+        let data = Matrix::from([[1, 2], [3, 4]]);
+        let d = MatrixDisplay::styled(&f, &data, |r, c, &x| {
+            Cell::new(
+                std::char::from_digit(x + r as u32 + c as u32, 10).unwrap(),
+                7,
+                0,
+            )
+        });
+        assert_eq!(vec!["13", "46"], screen(&d, &BordersStyle::None));
+    }
+    #[test]
+    fn readable_foreground() {
+        let f = Format::new(1, 1);
+        let mut m = Matrix::from([[Cell::new('a', 0, 17), Cell::new('b', 0, 231)]]);
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.set_readable_foreground(4.5);
+        let mut s = VirtualScreen::new();
+        d.print(&mut s, &BordersStyle::None);
+        assert_eq!(Some(ansi_term::Colour::Fixed(231)), s.fg((1, 1)));
+        assert_eq!(Some(ansi_term::Colour::Fixed(0)), s.fg((2, 1)));
+    }
+    #[test]
+    fn any_render_cell_is_displayed() {
+        use crate::cell::{AnsiColor, Attributes, RenderCell};
+        #[derive(Clone)]
+        struct Tile(u32);
+        impl RenderCell for Tile {
+            fn text(&self) -> String {
+                format!("{}", 1 << self.0)
+            }
+            fn color(&self) -> AnsiColor {
+                AnsiColor { fg: 0, bg: 3 }
+            }
+            fn attributes(&self) -> Attributes {
+                Attributes {
+                    bold: self.0 > 2,
+                    ..Attributes::default()
+                }
+            }
+        }
+        let f = Format::new(2, 1);
+        let mut m = Matrix::from([[Tile(1), Tile(3)]]);
+        let d = GenericMatrixDisplay::new(&f, &mut m);
+        let mut s = VirtualScreen::new();
+        d.print(&mut s, &BordersStyle::None);
+        assert_eq!(vec![" 2 8"], s.lines());
+        assert_eq!(Some(ansi_term::Colour::Fixed(3)), s.bg((2, 1)));
+        assert!(!s.cell((2, 1)).style.is_bold);
+        assert!(s.cell((4, 1)).style.is_bold);
+    }
+    #[test]
+    fn cells_viewport_is_closed() {
+        let f = Format::new(3, 1);
+        let mut m = letters();
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.set_viewport(Viewport::cells(2, 2).at(1, 1));
+        assert_eq!(
+            vec![
+                "┌───┬───┐",
+                "│ f │ g │",
+                "├───┼───┤",
+                "│ j │ k │",
+                "└───┴───┘"
+            ],
+            screen(&d, &BordersStyle::Light)
+        );
+    }
+    #[test]
+    fn viewport_stops_at_matrix_edges() {
+        let f = Format::new(1, 1);
+        let mut m = letters();
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.set_viewport(Viewport::cells(2, 3).at(5, 2));
+        assert_eq!(vec!["fgh", "jkl"], screen(&d, &BordersStyle::None));
+    }
+    #[test]
+    fn chars_viewport_truncates_edges() {
+        let f = Format::new(3, 1);
+        let mut m = letters();
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.set_viewport(Viewport::chars(11, 4).at(0, 1));
+        assert_eq!(
+            vec!["┌───┬───┬──", "│ b │ c │ d", "├───┼───┼──", "│ f │ g │ h"],
+            screen(&d, &BordersStyle::Light)
+        );
+    }
+    #[test]
+    fn chars_viewport_truncates_wide_characters() {
+        let f = Format::new(2, 1);
+        let mut m = Matrix::new(2, vec![Cell::new('中', 7, 0), Cell::new('文', 7, 0)]);
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.set_viewport(Viewport::chars(3, 1));
+        assert_eq!(vec!["中"], screen(&d, &BordersStyle::None));
+    }
+    #[test]
+    fn scroll_indicators() {
+        let f = Format::new(2, 1);
+        let mut m = letters();
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.set_viewport(Viewport::cells(1, 2).with_scroll_indicators());
+        assert_eq!(vec![" a b", "  ▼▶"], screen(&d, &BordersStyle::None));
+        d.scroll(1, 1);
+        assert_eq!(vec![" f g", "◀▲▼▶"], screen(&d, &BordersStyle::None));
+        d.scroll(1, 1);
+        assert_eq!(vec![" k l", "◀▲"], screen(&d, &BordersStyle::None));
+    }
+    #[test]
+    fn scrolling_stops_at_the_last_page() {
+        let f = Format::new(1, 1);
+        let mut m = letters();
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.set_viewport(Viewport::cells(2, 2));
+        d.scroll(10, 10);
+        assert_eq!((1, 2), (d.viewport.unwrap().row, d.viewport.unwrap().col));
+        assert_eq!(vec!["gh", "kl"], screen(&d, &BordersStyle::None));
+        d.scroll(-1, -1);
+        assert_eq!(vec!["bc", "fg"], screen(&d, &BordersStyle::None));
+    }
+    #[test]
+    fn chars_viewport_scrolls_to_the_last_cells_whatever_the_borders() {
+        let f = Format::new(1, 1);
+        let mut m = letters();
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.set_viewport(Viewport::chars(5, 5));
+        d.scroll(10, 10);
+        assert_eq!(
+            vec!["┌─┬─┐", "│g│h│", "├─┼─┤", "│k│l│", "└─┴─┘"],
+            screen(&d, &BordersStyle::Light)
+        );
+        assert_eq!(
+            vec!["abcd", "efgh", "ijkl"],
+            screen(&d, &BordersStyle::None)
+        );
+    }
+    #[test]
+    fn fit_viewport_keeps_position() {
+        let f = Format::new(1, 1);
+        let mut m = letters();
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.fit_viewport((2, 1));
+        assert_eq!(vec!["ab"], screen(&d, &BordersStyle::None));
+        d.scroll(1, 1);
+        d.fit_viewport((3, 2));
+        assert_eq!(vec!["fgh", "jkl"], screen(&d, &BordersStyle::None));
+    }
+    #[test]
+    fn hit_testing_goes_through_the_viewport() {
+        let f = Format::new(3, 2);
+        let mut m = letters();
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.set_viewport(Viewport::cells(2, 2).at(1, 2));
+        assert_eq!((2, 1), d.coordinates_at_cursor_position((1, 1)));
+        assert_eq!((3, 2), d.coordinates_at_cursor_position((4, 3)));
+        assert_eq!('l', d.cell_at_cursor_position((6, 4)).value);
+    }
+}
+
+#[cfg(test)]
+mod frozen_display_tests {
+    use super::Format;
+    use super::MatrixDisplay;
+    use crate::screen::fixtures::{letters, screen};
+    use crate::style::BordersStyle;
+    use crate::viewport::Viewport;
+    #[test]
+    fn separator_replaces_borders() {
+        let f = Format::new(1, 1);
+        let mut m = letters();
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.set_frozen(1, 1);
+        assert_eq!(
+            vec![
+                "┌─┳─┬─┬─┐",
+                "│a┃b│c│d│",
+                "┣━╋━╋━╋━┫",
+                "│e┃f│g│h│",
+                "├─╋─┼─┼─┤",
+                "│i┃j│k│l│",
+                "└─┻─┴─┴─┘"
+            ],
+            screen(&d, &BordersStyle::Light)
+        );
+    }
+    #[test]
+    fn separator_without_borders() {
+        let f = Format::new(1, 1);
+        let mut m = letters();
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.set_frozen(1, 1);
+        assert_eq!(
+            vec!["a┃bcd", "━╋━━━", "e┃fgh", "i┃jkl"],
+            screen(&d, &BordersStyle::None)
+        );
+    }
+    #[test]
+    fn frozen_cells_stay_while_scrolling() {
+        let f = Format::new(1, 1);
+        let mut m = letters();
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.set_frozen(1, 1);
+        d.set_viewport(Viewport::cells(2, 3).at(2, 2).with_scroll_indicators());
+        assert_eq!(
+            vec!["a┃cd", "━╋━━", "i┃kl", "◀▲"],
+            screen(&d, &BordersStyle::None)
+        );
+        // The viewport can't scroll the frozen cells away
+        d.scroll(-5, -5);
+        assert_eq!(
+            vec!["a┃bc", "━╋━━", "e┃fg", "  ▼▶"],
+            screen(&d, &BordersStyle::None)
+        );
+    }
+    #[test]
+    fn chars_viewport_leaves_room_for_the_separator() {
+        let f = Format::new(1, 1);
+        let mut m = letters();
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.set_frozen(1, 1);
+        d.set_viewport(Viewport::chars(4, 4).with_scroll_indicators());
+        assert_eq!(
+            vec!["a┃bc", "━╋━━", "e┃fg", "  ▼▶"],
+            screen(&d, &BordersStyle::None)
+        );
+    }
+    #[test]
+    fn heavy_borders_get_a_double_separator() {
+        let f = Format::new(1, 1);
+        let mut m = letters();
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.set_frozen(0, 1);
+        assert_eq!("┃a║b┃c┃d┃", screen(&d, &BordersStyle::Heavy)[1]);
+    }
+    #[test]
+    fn hit_testing_skips_the_separator() {
+        let f = Format::new(1, 1);
+        let mut m = letters();
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.set_frozen(1, 1);
+        d.set_viewport(Viewport::cells(2, 3).at(2, 2));
+        assert_eq!((0, 0), d.coordinates_at_cursor_position((1, 1)));
+        assert_eq!((0, 0), d.coordinates_at_cursor_position((2, 2)));
+        assert_eq!((2, 2), d.coordinates_at_cursor_position((3, 3)));
+        assert_eq!((3, 0), d.coordinates_at_cursor_position((4, 1)));
+    }
+}
+
+/// Paints the lines of a cell
+///
+/// The border on the left of the cell, the horizontal borders inside it and the border on its right
+/// (only drawn for the rightmost cell) may each use a different style,
+/// which is how separators between frozen and scrolling cells get drawn.
 struct CellDisplay<'a> {
-    borders: &'a BordersStyle,
+    left: &'a BordersStyle,
+    fill: &'a BordersStyle,
+    right: &'a BordersStyle,
+    /// Number of characters left on the line: the cell is cropped to fit in them
+    room: usize,
+    /// Whether it is the last cell of the line, which gets the border on the right
+    last: bool,
 }
 
 impl<'a> CellDisplay<'a> {
-    fn with_borders(b: &'a BordersStyle) -> CellDisplay {
-        CellDisplay { borders: b }
-    }
-    fn top_cell(&self, pos: &Position, cell_width: usize) -> Vec<ansi_term::ANSIString<'static>> {
+    fn top_cell(&self, pos: &Position, cell_width: usize) -> Line {
         if pos.top() {
             self.cell(
-                self.borders.top_left_corner(),
-                self.borders.top_intersection(),
-                self.borders.top_right_corner(),
-                self.borders.horizontal_border(),
+                (
+                    self.left.top_left_corner(),
+                    self.left.top_intersection(),
+                    self.right.top_right_corner(),
+                ),
+                self.fill.horizontal_border(),
                 "",
                 pos,
                 cell_width,
-                &AnsiColor::default(),
+                &plain_style(),
             )
         } else {
             self.cell(
-                self.borders.left_intersection(),
-                self.borders.intersection(),
-                self.borders.right_intersection(),
-                self.borders.horizontal_border(),
+                (
+                    self.left.left_intersection(),
+                    self.left.intersection(),
+                    self.right.right_intersection(),
+                ),
+                self.fill.horizontal_border(),
                 "",
                 pos,
                 cell_width,
-                &AnsiColor::default(),
+                &plain_style(),
             )
         }
     }
-    fn bottom_cell(
-        &self,
-        pos: &Position,
-        cell_width: usize,
-    ) -> Vec<ansi_term::ANSIString<'static>> {
-        if pos.bottom() {
-            self.cell(
-                self.borders.bottom_left_corner(),
-                self.borders.bottom_intersection(),
-                self.borders.bottom_right_corner(),
-                self.borders.horizontal_border(),
-                "",
-                pos,
-                cell_width,
-                &AnsiColor::default(),
-            )
+    /// The border below a cell of the last row
+    fn bottom_cell(&self, pos: &Position, cell_width: usize) -> Line {
+        self.cell(
+            (
+                self.left.bottom_left_corner(),
+                self.left.bottom_intersection(),
+                self.right.bottom_right_corner(),
+            ),
+            self.fill.horizontal_border(),
+            "",
+            pos,
+            cell_width,
+            &plain_style(),
+        )
+    }
+    /// The border above a cell missing from a ragged last row, closing the bottom of the cell above it
+    ///
+    /// `first` tells whether it is the first missing cell, whose left border still meets the ragged row's.
+    fn missing_cell(&self, pos: &Position, cell_width: usize, first: bool) -> Line {
+        let left = if first {
+            self.left.intersection()
         } else {
-            Vec::new()
-        }
+            self.left.bottom_intersection()
+        };
+        self.cell(
+            (left, left, self.right.bottom_right_corner()),
+            self.fill.horizontal_border(),
+            "",
+            pos,
+            cell_width,
+            &plain_style(),
+        )
     }
-    fn padding_cell(
-        &self,
-        pos: &Position,
-        cell_width: usize,
-        color: &AnsiColor,
-    ) -> Vec<ansi_term::ANSIString<'static>> {
-        self.value_cell(pos, cell_width, " ", color)
+    fn padding_cell(&self, pos: &Position, cell_width: usize, style: &Style) -> Line {
+        self.value_cell(pos, cell_width, " ", style)
     }
-    fn value_cell(
-        &self,
-        pos: &Position,
-        cell_width: usize,
-        content: &str,
-        color: &AnsiColor,
-    ) -> Vec<ansi_term::ANSIString<'static>> {
+    fn value_cell(&self, pos: &Position, cell_width: usize, content: &str, style: &Style) -> Line {
         self.cell(
-            self.borders.vertical_border(),
-            self.borders.vertical_border(),
-            self.borders.vertical_border(),
+            (
+                self.left.vertical_border(),
+                self.left.vertical_border(),
+                self.right.vertical_border(),
+            ),
             ' ',
             content,
             pos,
             cell_width,
-            color,
+            style,
         )
     }
+    /// Paint one line of one cell: its left border, its inside and, for the rightmost cell, its
+    /// right border
+    ///
+    /// `BordersStyle::None` uses '\0' as its border characters: those are skipped entirely.
+    /// Whatever doesn't fit in `room` is cropped.
     fn cell(
         &self,
-        left: char,
-        middle: char,
-        right: char,
+        (left, middle, right): (char, char, char),
         fill: char,
         content: &str,
         pos: &Position,
         width: usize,
-        color: &AnsiColor,
-    ) -> Vec<ansi_term::ANSIString<'static>> {
+        style: &Style,
+    ) -> Line {
         let mut left_border = String::new();
         if pos.left() {
-            left_border += &left.to_string();
+            left_border.push(left);
         } else {
-            left_border += &middle.to_string();
+            left_border.push(middle);
         }
         let inside = horizontal_pad(width, content, fill);
         let mut right_border = String::new();
-        if pos.right() {
-            right_border += &right.to_string();
-            if self.borders != &BordersStyle::None || content != "" {
-                right_border += "\r\n";
-            }
+        if self.last {
+            right_border.push(right);
         }
-        let plain = AnsiColor::default();
+        let plain = plain_style();
+        let mut room = self.room;
+        let mut crop = |text: String| {
+            let text = crop_text(&text.replace('\0', ""), room);
+            room -= UnicodeWidthStr::width(&text as &str);
+            text
+        };
 
         vec![
-            Fixed(plain.fg).on(Fixed(plain.bg)).paint(left_border),
-            Fixed(color.fg).on(Fixed(color.bg)).paint(inside),
-            Fixed(plain.fg).on(Fixed(plain.bg)).paint(right_border),
+            plain.paint(crop(left_border)),
+            style.paint(crop(inside)),
+            plain.paint(crop(right_border)),
         ]
     }
 }
 
+/// The style of text in the given colors
+fn color_style(color: &AnsiColor) -> Style {
+    Fixed(color.fg).on(Fixed(color.bg))
+}
+
+/// The style of borders and of anything outside cells
+fn plain_style() -> Style {
+    color_style(&AnsiColor::default())
+}
+
+/// One line of rendered output, without its line terminator
+type Line = Vec<ansi_term::ANSIString<'static>>;
+
+/// Number of characters taken by a border with the given style
+fn border_width(borders: &BordersStyle) -> usize {
+    if *borders == BordersStyle::None {
+        0
+    } else {
+        1
+    }
+}
+
+/// Number of characters taken by the separator after `frozen` frozen rows or columns
+///
+/// The separator replaces a border, so it only takes room of its own with `BordersStyle::None`.
+fn separator_width(borders: &BordersStyle, frozen: usize) -> usize {
+    if frozen > 0 && border_width(borders) == 0 {
+        1
+    } else {
+        0
+    }
+}
+
+/// Join rendered lines, terminating each of them with "\r\n"
+fn join_lines(lines: Vec<Line>) -> Vec<ansi_term::ANSIString<'static>> {
+    lines
+        .into_iter()
+        .flat_map(|mut line| {
+            line.push(plain_style().paint("\r\n"));
+            line
+        })
+        .collect::<Vec<_>>()
+}
+
+/// Number of cells, possibly truncated, needed to fill a length in characters
+fn cells_in_chars(chars: usize, cell_dim: usize, border: usize) -> usize {
+    let chars = chars.saturating_sub(border);
+    chars.div_ceil(cell_dim + border)
+}
+
+/// Display width of a rendered line
+fn line_width(line: &Line) -> usize {
+    line.iter()
+        .map(|s| UnicodeWidthStr::width(&**s as &str))
+        .sum()
+}
+
+/// Keep the first `width` characters of a text
+///
+/// A double width character that would straddle the edge is replaced with a blank.
+fn crop_text(text: &str, width: usize) -> String {
+    let mut remaining = width;
+    let mut cropped = String::new();
+    for c in text.chars() {
+        let w = UnicodeWidthChar::width(c).unwrap_or(0);
+        if w > remaining {
+            cropped.push_str(&" ".repeat(remaining));
+            break;
+        }
+        remaining -= w;
+        cropped.push(c);
+    }
+    cropped
+}
+
+/// A line pointing towards the directions where more cells can be scrolled into view
+///
+/// ◀ and ▶ sit at both ends of the line, ▲ and ▼ in its middle.
+fn scroll_indicators(width: usize, (up, down): (bool, bool), (left, right): (bool, bool)) -> Line {
+    let mut indicators = vec![' '; std::cmp::max(width, 4)];
+    let middle = indicators.len() / 2;
+    let n = indicators.len();
+    if left {
+        indicators[0] = '◀';
+    }
+    if up {
+        indicators[middle - 1] = '▲';
+    }
+    if down {
+        indicators[middle] = '▼';
+    }
+    if right {
+        indicators[n - 1] = '▶';
+    }
+    vec![plain_style().paint(indicators.into_iter().collect::<String>())]
+}
+
 fn cursor_to_index(x: usize, cell_dim: usize, n_cells: usize) -> usize {
     (std::cmp::min(x, n_cells * cell_dim) - 1) / cell_dim
 }
 
+/// Index within a band of the cell under the cursor, the separator counting as the last frozen cell
+fn cursor_to_band_index(x: usize, cell_dim: usize, band: &Band) -> usize {
+    let x = match band.split() {
+        Some(split) if x > split * cell_dim => x - 1,
+        _ => x,
+    };
+    cursor_to_index(x, cell_dim, band.len())
+}
+
+/// The first scrolling cell shown when scrolling to `first`, out of `n` cells of which `frozen` are frozen,
+/// in a window of `capacity` cells, frozen cells included
+///
+/// The frozen cells can't be scrolled away, and scrolling stops once the last cell is in view
+/// so that the window stays full.
+fn first_scrolling(n: usize, frozen: usize, first: usize, capacity: usize) -> usize {
+    let frozen = std::cmp::min(frozen, n);
+    let scrolling = std::cmp::max(1, capacity.saturating_sub(frozen));
+    std::cmp::max(frozen, std::cmp::min(first, n.saturating_sub(scrolling)))
+}
+
+/// The rows, or the columns, of the matrix that are on screen:
+/// the frozen ones first, then a range of the scrolling ones
+struct Band {
+    frozen: usize,
+    scrolling: Range<usize>,
+}
+impl Band {
+    /// The band of `n` cells with `frozen` frozen cells and scrolling cells starting from `first`
+    ///
+    /// When given, `capacity` limits the total number of cells, frozen cells included.
+    fn new(n: usize, frozen: usize, first: usize, capacity: Option<usize>) -> Band {
+        let frozen = std::cmp::min(frozen, n);
+        let start = std::cmp::max(frozen, std::cmp::min(first, n));
+        let end = match capacity {
+            Some(capacity) => std::cmp::min(start + capacity.saturating_sub(frozen), n),
+            None => n,
+        };
+        Band {
+            frozen,
+            scrolling: start..std::cmp::max(start, end),
+        }
+    }
+    fn len(&self) -> usize {
+        self.frozen + self.scrolling.len()
+    }
+    fn indices(&self) -> Vec<usize> {
+        (0..self.frozen).chain(self.scrolling.clone()).collect()
+    }
+    /// Index within the matrix of the i-th cell of the band
+    fn index(&self, i: usize) -> usize {
+        if i < self.frozen {
+            i
+        } else {
+            self.scrolling.start + i - self.frozen
+        }
+    }
+    /// Index within the band of the first scrolling cell, if a separator is needed before it
+    fn split(&self) -> Option<usize> {
+        if self.frozen > 0 && !self.scrolling.is_empty() {
+            Some(self.frozen)
+        } else {
+            None
+        }
+    }
+    /// Whether some scrolling cells are hidden before the visible ones
+    fn scrolled_back(&self) -> bool {
+        self.scrolling.start > self.frozen
+    }
+}
+
+/// A display of `Cell`s holding values of type T, the most common kind of display
+///
+/// Its methods are those of `GenericMatrixDisplay`, which displays any type implementing `RenderCell`.
+pub type MatrixDisplay<'a, T> = GenericMatrixDisplay<'a, Cell<T>>;
+
 /// Stores a matrix of data and offers a way to pretty print it
 ///
+/// `MatrixDisplay<T>` names the display of `Cell<T>`s: spell out `GenericMatrixDisplay<C>`
+/// for other cell types.
+///
 /// #Example: visualising a 256 colors palette:
 /// ```
 /// use matrix_display::*;
@@ -268,31 +893,96 @@ fn cursor_to_index(x: usize, cell_dim: usize, n_cells: usize) -> usize {
 /// let display = MatrixDisplay::new(&format, &mut data);
 /// display.print(&mut std::io::stdout(), &style::BordersStyle::Light);
 /// ```
-pub struct MatrixDisplay<'a, T>
+pub struct GenericMatrixDisplay<'a, C>
 where
-    T: Clone,
-    T: ToString + 'a,
+    C: RenderCell + Clone + 'a,
 {
     fmt: &'a Format,
-    mat: &'a mut Matrix<Cell<T>>,
+    mat: Source<'a, C>,
+    viewport: Option<Viewport>,
+    frozen: (usize, usize),
+    legend: Option<(Legend, LegendPosition)>,
+    readable: Option<f64>,
+    rules: Option<Rules<'a, C>>,
+    banding: Option<Banding>,
 }
-impl<'a, T> MatrixDisplay<'a, T>
+impl<'a, C> GenericMatrixDisplay<'a, C>
 where
-    T: Clone,
-    T: ToString + 'a,
+    C: RenderCell + Clone + 'a,
 {
     /// Construct a matrix display
     ///
     /// f: the format of a cell (width, height)
     /// m: a reference to the data (&Matrix<Cell>)
-    pub fn new(f: &'a Format, m: &'a mut Matrix<Cell<T>>) -> MatrixDisplay<'a, T> {
-        MatrixDisplay { fmt: f, mat: m }
+    pub fn new(f: &'a Format, m: &'a mut Matrix<C>) -> GenericMatrixDisplay<'a, C> {
+        GenericMatrixDisplay::from_view_mut(f, m.view_mut(.., ..))
+    }
+    /// Construct a display of a region of a matrix, without copying it
+    ///
+    /// The view is read only, so `try_cell_at_cursor_position` returns None on this display.
+    ///
+    /// # Example: printing the top left corner of a large matrix
+    /// ```
+    /// use matrix_display::*;
+    /// let format = Format::new(5, 1);
+    /// let data = matrix::Matrix::from_fn(100, 100, |r, c| cell::Cell::new(r * c, 7, 0));
+    /// let display = MatrixDisplay::from_view(&format, data.view(..4, ..8));
+    /// display.print(&mut std::io::stdout(), &style::BordersStyle::Light);
+    /// ```
+    pub fn from_view(f: &'a Format, view: MatrixView<'a, C>) -> GenericMatrixDisplay<'a, C> {
+        GenericMatrixDisplay::with_source(f, Source::Shared(view))
+    }
+    /// Construct a display of a mutable region of a matrix, without copying it
+    pub fn from_view_mut(f: &'a Format, view: MatrixViewMut<'a, C>) -> GenericMatrixDisplay<'a, C> {
+        GenericMatrixDisplay::with_source(f, Source::Mutable(view))
+    }
+    /// Construct a display of a matrix of data, styled on the fly by `style(row, col, value)`
+    ///
+    /// Cells are only styled when rendered, so a viewport onto a large matrix only styles the visible cells.
+    /// Styled cells can't be modified through `try_cell_at_cursor_position`, which returns None: modify the data instead.
+    ///
+    /// # Example: displaying a 2048 board
+    /// ```
+    /// use matrix_display::*;
+    /// let format = Format::new(6, 1);
+    /// let board = matrix::Matrix::from([[2, 0], [4, 2048]]);
+    /// let display = MatrixDisplay::styled(&format, &board, |_, _, &x| {
+    ///     cell::Cell::new(x, 0, if x == 0 { 7 } else { 3 })
+    /// });
+    /// display.print(&mut std::io::stdout(), &style::BordersStyle::Light);
+    /// ```
+    pub fn styled<D, F>(f: &'a Format, data: &'a Matrix<D>, style: F) -> GenericMatrixDisplay<'a, C>
+    where
+        D: Clone,
+        F: Fn(usize, usize, &D) -> C + 'a,
+    {
+        GenericMatrixDisplay::with_source(
+            f,
+            Source::Styled {
+                n_rows: data.n_rows(),
+                n_cols: data.n_cols(),
+                last_row_len: data.last_row_len(),
+                style: Box::new(move |r, c| style(r, c, &data[(r, c)])),
+            },
+        )
+    }
+    fn with_source(f: &'a Format, mat: Source<'a, C>) -> GenericMatrixDisplay<'a, C> {
+        GenericMatrixDisplay {
+            fmt: f,
+            mat,
+            viewport: None,
+            frozen: (0, 0),
+            legend: None,
+            readable: None,
+            rules: None,
+            banding: None,
+        }
     }
     fn n_rows(&self) -> usize {
-        self.mat.n_rows()
+        self.mat.shape().0
     }
     fn n_cols(&self) -> usize {
-        self.mat.n_cols()
+        self.mat.shape().1
     }
     /// The matrix's width in number of characters
     pub fn width(&self) -> usize {
@@ -302,57 +992,308 @@ where
     pub fn height(&self) -> usize {
         self.n_rows() * self.fmt.cell_h
     }
-    fn print_top(
-        &self,
-        borders: &BordersStyle,
-        row: &[(Cell<T>, Position)],
-    ) -> Vec<ansi_term::ANSIString<'static>> {
-        row.iter()
-            .flat_map(|&(_, ref pos)| {
-                CellDisplay::with_borders(borders).top_cell(pos, self.fmt.cell_w)
-            })
-            .collect::<Vec<_>>()
-    }
-    fn print_vertical_pad(
-        &self,
-        borders: &BordersStyle,
-        row: &[(Cell<T>, Position)],
-        pad: usize,
-    ) -> Vec<ansi_term::ANSIString<'static>> {
-        row.iter()
-            .cycle()
-            .take(pad * row.len())
-            .flat_map(|&(ref cell, ref pos)| {
-                CellDisplay::with_borders(borders).padding_cell(pos, self.fmt.cell_w, &cell.color)
-            })
-            .collect::<Vec<_>>()
-    }
-    fn print_value_row(
+    /// The style of the inside of a cell: its colors and attributes, adjusted by the display's banding, rules and options
+    ///
+    /// `cutoffs` are those of the display's rules, if any.
+    fn cell_style(&self, cell: &C, (row, col): (usize, usize), cutoffs: &[Option<f64>]) -> Style {
+        let highlights = match self.rules {
+            Some(ref rules) => {
+                rules.highlights(cell, (row, col), cutoffs, |r, c| self.mat.cell(r, c))
+            }
+            None => Vec::new(),
+        };
+        let mut color = cell.color();
+        if let Some(ref banding) = self.banding {
+            banding.tint(&mut color, (row, col));
+        }
+        for highlight in &highlights {
+            highlight.recolor(&mut color);
+        }
+        if let Some(threshold) = self.readable {
+            color = color.readable(threshold);
+        }
+        let style = cell.attributes().apply(color_style(&color));
+        highlights
+            .iter()
+            .fold(style, |style, highlight| highlight.attributes.apply(style))
+    }
+    /// Paint one line of a row of cells, cropped to `width` characters
+    ///
+    /// `edges` is the style of the borders on each side of the cells, `fill` the style of the horizontal
+    /// borders across them, and `separator` the style of the border between frozen and scrolling columns.
+    fn print_line<T, F>(
         &self,
-        borders: &BordersStyle,
-        row: &[(Cell<T>, Position)],
-    ) -> Vec<ansi_term::ANSIString<'static>> {
-        row.iter()
-            .flat_map(|&(ref cell, ref pos)| {
-                CellDisplay::with_borders(borders).value_cell(
-                    pos,
-                    self.fmt.cell_w,
-                    &cell.clone().value.to_string(),
-                    &cell.color,
+        row: &[(T, Position)],
+        split: Option<usize>,
+        (edges, fill, separator): (&BordersStyle, &BordersStyle, &BordersStyle),
+        width: usize,
+        paint: F,
+    ) -> Line
+    where
+        F: Fn(&CellDisplay, &T, &Position) -> Line,
+    {
+        let mut line = Line::new();
+        let mut used = 0;
+        for (j, (cell, pos)) in row.iter().enumerate() {
+            if used >= width {
+                break;
+            }
+            let left = if split == Some(j) { separator } else { edges };
+            let display = CellDisplay {
+                left,
+                fill,
+                right: edges,
+                room: width - used,
+                last: j + 1 == row.len(),
+            };
+            let painted = paint(&display, cell, pos);
+            used += line_width(&painted);
+            line.extend(painted);
+        }
+        line
+    }
+    /// The rows and columns of the matrix that are visible through the viewport, if any
+    fn visible(&self, borders: &BordersStyle) -> (Band, Band) {
+        let (frozen_rows, frozen_cols) = self.frozen;
+        match self.viewport {
+            None => (
+                Band::new(self.n_rows(), frozen_rows, 0, None),
+                Band::new(self.n_cols(), frozen_cols, 0, None),
+            ),
+            Some(ref v) => {
+                let (rows, cols) = self.capacity(v, borders);
+                let first_row = first_scrolling(self.n_rows(), frozen_rows, v.row, rows);
+                let first_col = first_scrolling(self.n_cols(), frozen_cols, v.col, cols);
+                (
+                    Band::new(self.n_rows(), frozen_rows, first_row, Some(rows)),
+                    Band::new(self.n_cols(), frozen_cols, first_col, Some(cols)),
                 )
-            })
-            .collect::<Vec<_>>()
+            }
+        }
+    }
+    /// The number of rows and columns, possibly truncated, shown through a viewport
+    fn capacity(&self, v: &Viewport, borders: &BordersStyle) -> (usize, usize) {
+        let (frozen_rows, frozen_cols) = self.frozen;
+        let b = border_width(borders);
+        match v.size {
+            ViewportSize::Cells { rows, cols } => (rows, cols),
+            ViewportSize::Chars { width, height } => {
+                let height = if v.scroll_indicators {
+                    height.saturating_sub(1)
+                } else {
+                    height
+                };
+                let height = height.saturating_sub(separator_width(borders, frozen_rows));
+                let width = width.saturating_sub(separator_width(borders, frozen_cols));
+                (
+                    cells_in_chars(height, self.fmt.cell_h, b),
+                    cells_in_chars(width, self.fmt.cell_w, b),
+                )
+            }
+        }
     }
-    fn print_bottom(
+    /// Render the given rows and columns of the matrix as if they were a matrix on their own
+    ///
+    /// A separator is drawn between frozen and scrolling cells. With `BordersStyle::None`, it takes
+    /// an extra line or column; otherwise it replaces the border that would be there.
+    /// When given, `width` crops the lines to that many characters.
+    fn render_region(
         &self,
         borders: &BordersStyle,
-        row: &[(Cell<T>, Position)],
-    ) -> Vec<ansi_term::ANSIString<'static>> {
-        row.iter()
-            .flat_map(|&(_, ref pos)| {
-                CellDisplay::with_borders(borders).bottom_cell(pos, self.fmt.cell_w)
-            })
-            .collect::<Vec<_>>()
+        rows: &Band,
+        cols: &Band,
+        width: Option<usize>,
+    ) -> Vec<Line> {
+        let width = width.unwrap_or(usize::MAX);
+        let vertical_pad = Pad::new(self.fmt.cell_h, 1);
+        let has_borders = border_width(borders) == 1;
+        let separator = borders.separator();
+        let no_borders = BordersStyle::None;
+        let separator_edges = if has_borders { &separator } else { &no_borders };
+        let cell_w = self.fmt.cell_w;
+        let col_indices = cols.indices();
+        let split = cols.split();
+        let cutoffs = match self.rules {
+            Some(ref rules) => {
+                rules.cutoffs(self.n_rows(), self.n_cols(), |r, c| self.mat.cell(r, c))
+            }
+            None => Vec::new(),
+        };
+        // The visible cells of a row come first, the ragged last row may stop before the others
+        let shown = |r: usize| {
+            col_indices
+                .iter()
+                .take_while(|&&c| c < self.mat.row_len(r))
+                .count()
+        };
+        let mut row_indices = rows.indices();
+        if !col_indices.is_empty() && row_indices.last().map(|&r| shown(r)) == Some(0) {
+            row_indices.pop();
+        }
+        let n = row_indices.len();
+        let mut lines = Vec::new();
+        for (i, &r) in row_indices.iter().enumerate() {
+            let k = shown(r);
+            let row = col_indices[..k]
+                .iter()
+                .enumerate()
+                .map(|(j, &c)| {
+                    let cell = self.mat.cell(r, c);
+                    let style = self.cell_style(&cell, (r, c), &cutoffs);
+                    ((cell, style), Position::within((i, j), (n, k)))
+                })
+                .collect::<Vec<_>>();
+            // The line above a ragged row spans the row above it, whose bottom it closes
+            let above = if i == 0 { k } else { col_indices.len() };
+            let top_row = (0..above)
+                .map(|j| (j, Position::within((i, j), (n, above))))
+                .collect::<Vec<_>>();
+            let top = |d: &CellDisplay, &j: &usize, pos: &Position| {
+                if j < k {
+                    d.top_cell(pos, cell_w)
+                } else {
+                    d.missing_cell(pos, cell_w, j == k)
+                }
+            };
+            let padding = |d: &CellDisplay, (_, style): &(C, Style), pos: &Position| {
+                d.padding_cell(pos, cell_w, style)
+            };
+            let value = |d: &CellDisplay, (cell, style): &(C, Style), pos: &Position| {
+                d.value_cell(pos, cell_w, &cell.text(), style)
+            };
+            let bottom =
+                |d: &CellDisplay, _: &(C, Style), pos: &Position| d.bottom_cell(pos, cell_w);
+            let regular = (borders, borders, &separator);
+            if rows.split() == Some(i) {
+                lines.push(self.print_line(
+                    &top_row,
+                    split,
+                    (separator_edges, &separator, &separator),
+                    width,
+                    top,
+                ));
+            } else if has_borders {
+                lines.push(self.print_line(&top_row, split, regular, width, top));
+            }
+            for _ in 0..vertical_pad.before {
+                lines.push(self.print_line(&row, split, regular, width, padding));
+            }
+            lines.push(self.print_line(&row, split, regular, width, value));
+            for _ in 0..vertical_pad.after {
+                lines.push(self.print_line(&row, split, regular, width, padding));
+            }
+            if has_borders && i + 1 == n {
+                lines.push(self.print_line(&row, split, regular, width, bottom));
+            }
+        }
+        lines
+    }
+    /// Render the visible part of the matrix into lines of ANSIStrings, without line terminators
+    fn render_lines(&self, borders: &BordersStyle) -> Vec<Line> {
+        let (rows, cols) = self.visible(borders);
+        let width = match self.viewport {
+            Some(Viewport {
+                size: ViewportSize::Chars { width, .. },
+                ..
+            }) => Some(width),
+            _ => None,
+        };
+        let mut lines = self.render_region(borders, &rows, &cols, width);
+        if let Some(ref v) = self.viewport {
+            if let ViewportSize::Chars { height, .. } = v.size {
+                let height = if v.scroll_indicators {
+                    height.saturating_sub(1)
+                } else {
+                    height
+                };
+                lines.truncate(height);
+            }
+            if v.scroll_indicators {
+                let width = lines.first().map(line_width).unwrap_or(0);
+                lines.push(scroll_indicators(
+                    width,
+                    (rows.scrolled_back(), rows.scrolling.end < self.n_rows()),
+                    (cols.scrolled_back(), cols.scrolling.end < self.n_cols()),
+                ));
+            }
+        }
+        if let Some((ref legend, position)) = self.legend {
+            lines = legend.attach(lines, borders, position);
+        }
+        lines
+    }
+    /// Pin the first rows and columns in place while the rest of the matrix scrolls
+    ///
+    /// This is typically used to keep header rows or columns visible, like a spreadsheet's freeze panes.
+    /// A separator line is drawn between frozen and scrolling cells, using `BordersStyle::separator`.
+    /// A viewport's first row and column then refer to the first scrolling row and column.
+    pub fn set_frozen(&mut self, rows: usize, cols: usize) {
+        self.frozen = (rows, cols);
+    }
+    /// Show a window of the matrix instead of the whole matrix
+    pub fn set_viewport(&mut self, viewport: Viewport) {
+        self.viewport = Some(viewport);
+    }
+    /// Show as much of the matrix as fits within (width, height) characters
+    ///
+    /// The viewport keeps its position and scroll indicators, if any. Call this again when the terminal
+    /// gets resized.
+    pub fn fit_viewport(&mut self, (width, height): (usize, usize)) {
+        let size = ViewportSize::Chars { width, height };
+        self.viewport = Some(match self.viewport {
+            Some(v) => Viewport { size, ..v },
+            None => Viewport::chars(width, height),
+        });
+    }
+    /// Draw a legend beside or below the matrix, framed with the same borders
+    ///
+    /// The legend is sized after the visible part of the matrix and doesn't affect hit testing.
+    pub fn set_legend(&mut self, legend: Legend, position: LegendPosition) {
+        self.legend = Some((legend, position));
+    }
+    /// Replace the foreground of cells that don't contrast with their background by at least `threshold`
+    /// with black or white, whichever is more readable
+    ///
+    /// WCAG recommends a threshold of 4.5 for text. To only adjust some cells, use `AnsiColor::readable` instead.
+    /// The system colours 0 to 15 are assumed to have their usual xterm values, see `palette`.
+    pub fn set_readable_foreground(&mut self, threshold: f64) {
+        self.readable = Some(threshold);
+    }
+    /// Alternate the background of rows, columns or cells, see `Banding`
+    pub fn set_banding(&mut self, banding: Banding) {
+        self.banding = Some(banding);
+    }
+    /// Restyle the cells meeting conditions, at render time
+    ///
+    /// See `rules::Rules` for the order in which rules and the cells' own colors apply.
+    pub fn set_rules(&mut self, rules: Rules<'a, C>) {
+        self.rules = Some(rules);
+    }
+    /// Scroll the viewport, if any, by a number of rows and columns
+    ///
+    /// The viewport stops at the first scrolling row and column, and once the last row and column
+    /// of the matrix are in view, so it stays full and scrolling back always moves the visible cells.
+    /// A viewport sized in characters stops as if there were borders, so that the last cells can be
+    /// reached whatever the borders it gets printed with.
+    pub fn scroll(&mut self, rows: isize, cols: isize) {
+        if let Some(mut v) = self.viewport {
+            let (n_rows, n_cols) = self.mat.shape();
+            let (frozen_rows, frozen_cols) = self.frozen;
+            // Borders take room, so fewer cells fit with any of them than without
+            let (capacity_rows, capacity_cols) = self.capacity(&v, &BordersStyle::Light);
+            let clamp = |v: &mut Viewport| {
+                v.row = first_scrolling(n_rows, frozen_rows, v.row, capacity_rows);
+                v.col = first_scrolling(n_cols, frozen_cols, v.col, capacity_cols);
+            };
+            clamp(&mut v);
+            v.scroll(rows, cols);
+            clamp(&mut v);
+            self.viewport = Some(v);
+        }
+    }
+    /// The viewport, if any, to move or resize it
+    pub fn viewport_mut(&mut self) -> Option<&mut Viewport> {
+        self.viewport.as_mut()
     }
     /// Render a matrix into a Vec<ANSIString>.
     ///
@@ -360,40 +1301,106 @@ where
     /// you're good to go!
     /// This approach allows the user to customize how to display the matrix.
     pub fn render(&self, borders: &BordersStyle) -> Vec<ansi_term::ANSIString<'static>> {
-        let vertical_pad = Pad::new(self.fmt.cell_h, 1);
-        self.mat
-            .enumerate_cells()
-            .chunks(self.n_cols())
-            .into_iter()
-            .flat_map(|row| {
-                let mut all_strings = self.print_top(borders, row);
-                all_strings.append(&mut self.print_vertical_pad(borders, row, vertical_pad.before));
-                all_strings.append(&mut self.print_value_row(borders, row));
-                all_strings.append(&mut self.print_vertical_pad(borders, row, vertical_pad.after));
-                all_strings.append(&mut self.print_bottom(borders, row));
-                all_strings
-                //    row
-            })
-            .collect::<Vec<_>>()
+        join_lines(self.render_lines(borders))
     }
     /// Print a matrix. This is the most important method of this library
     ///
     /// Pick a BorderStyle, an output that implements the Write trait and
     /// you're good to go!
     pub fn print<Out: Write>(&self, out: &mut Out, borders: &BordersStyle) {
-        write!(out, "{}", ansi_term::ANSIStrings(&*self.render(borders))).unwrap();
+        write!(out, "{}", ansi_term::ANSIStrings(&self.render(borders))).unwrap();
     }
     /// Takes a cursor position in (usize, usize) and returns the coordinates of the cell under the cursor
+    ///
+    /// When a viewport is set, the cursor position is relative to the viewport and the coordinates
+    /// are those of the cell within the whole matrix.
+    /// Like the rest of hit testing, this ignores borders: with frozen cells, the separator is expected
+    /// to take one line and one column, as it does with `BordersStyle::None`.
     pub fn coordinates_at_cursor_position(&self, (x, y): (usize, usize)) -> (usize, usize) {
-        let col = cursor_to_index(x, self.fmt.cell_w, self.mat.n_cols());
-        let row = cursor_to_index(y as usize, self.fmt.cell_h, self.mat.n_rows());
+        // Hit testing ignores borders, so any border style works
+        let (rows, cols) = self.visible(&BordersStyle::None);
+        let col = cols.index(cursor_to_band_index(x, self.fmt.cell_w, &cols));
+        let row = rows.index(cursor_to_band_index(y, self.fmt.cell_h, &rows));
         (col, row)
     }
     /// Takes a cursor position in characters (x, y) and returns a mutable reference to the corresponding cell
     ///
-    /// This is used to modify a cell that was clicked
-    pub fn cell_at_cursor_position(&mut self, cursor: (usize, usize)) -> &mut Cell<T> {
-        let coord = self.coordinates_at_cursor_position(cursor);
-        self.mat.at(coord)
+    /// This is used to modify a cell that was clicked.
+    ///
+    /// # Panics
+    /// When the display doesn't have mutable cells: when it was constructed from a read only view
+    /// or from styled data. Use `try_cell_at_cursor_position` for those.
+    pub fn cell_at_cursor_position(&mut self, cursor: (usize, usize)) -> &mut C {
+        match self.try_cell_at_cursor_position(cursor) {
+            Some(cell) => cell,
+            None => panic!("the cells of a read only or styled display can't be modified"),
+        }
+    }
+    /// Takes a cursor position in characters (x, y) and returns a mutable reference to the corresponding cell,
+    /// or None when the display doesn't have mutable cells
+    ///
+    /// A display constructed from a read only view or from styled data can't modify its cells:
+    /// use `coordinates_at_cursor_position` to find the clicked cell in the data instead.
+    pub fn try_cell_at_cursor_position(&mut self, cursor: (usize, usize)) -> Option<&mut C> {
+        let (col, row) = self.coordinates_at_cursor_position(cursor);
+        match self.mat {
+            Source::Mutable(ref mut view) => Some(&mut view[(row, col)]),
+            Source::Shared(_) | Source::Styled { .. } => None,
+        }
+    }
+}
+
+/// The cells shown by a display: a region of a matrix, possibly the whole of it,
+/// or data styled on the fly
+enum Source<'a, C>
+where
+    C: RenderCell + Clone,
+{
+    Shared(MatrixView<'a, C>),
+    Mutable(MatrixViewMut<'a, C>),
+    Styled {
+        n_rows: usize,
+        n_cols: usize,
+        last_row_len: usize,
+        style: Box<dyn Fn(usize, usize) -> C + 'a>,
+    },
+}
+impl<'a, C> Source<'a, C>
+where
+    C: RenderCell + Clone,
+{
+    /// (n_rows, n_cols)
+    fn shape(&self) -> (usize, usize) {
+        match *self {
+            Source::Shared(ref view) => (view.n_rows(), view.n_cols()),
+            Source::Mutable(ref view) => (view.n_rows(), view.n_cols()),
+            Source::Styled { n_rows, n_cols, .. } => (n_rows, n_cols),
+        }
+    }
+    /// Number of cells displayed in a row, see `Matrix::ragged`
+    fn row_len(&self, row: usize) -> usize {
+        match *self {
+            Source::Shared(ref view) => view.row_len(row),
+            Source::Mutable(ref view) => view.as_view().row_len(row),
+            Source::Styled {
+                n_rows,
+                n_cols,
+                last_row_len,
+                ..
+            } => {
+                if row + 1 == n_rows {
+                    last_row_len
+                } else {
+                    n_cols
+                }
+            }
+        }
+    }
+    fn cell(&self, row: usize, col: usize) -> C {
+        match *self {
+            Source::Shared(ref view) => view[(row, col)].clone(),
+            Source::Mutable(ref view) => view[(row, col)].clone(),
+            Source::Styled { ref style, .. } => style(row, col),
+        }
     }
 }
@@ -0,0 +1,65 @@
+//! Errors raised when data doesn't have the shape of a matrix
+
+use std::fmt;
+
+/// Why some data couldn't be turned into a `Matrix`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MatrixError {
+    /// Cells were given for a matrix without any column
+    ZeroColumns,
+    /// The number of cells isn't a multiple of the number of columns, so the last row is incomplete
+    IncompleteRow { len: usize, n_cols: usize },
+    /// The number of cells doesn't match the number of rows and columns
+    WrongLength {
+        len: usize,
+        n_rows: usize,
+        n_cols: usize,
+    },
+    /// A matrix doesn't have the (n_rows, n_cols) shape it is converted to
+    ShapeMismatch {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    /// A row doesn't have the same length as the first row
+    RaggedRow {
+        row: usize,
+        len: usize,
+        n_cols: usize,
+    },
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MatrixError::ZeroColumns => write!(f, "cells were given for a matrix with 0 columns"),
+            MatrixError::IncompleteRow { len, n_cols } => write!(
+                f,
+                "{} cells can't be split into rows of {} columns: the last row would have {} cells",
+                len,
+                n_cols,
+                len % n_cols
+            ),
+            MatrixError::WrongLength {
+                len,
+                n_rows,
+                n_cols,
+            } => write!(
+                f,
+                "{} cells were given for a matrix of {} rows and {} columns",
+                len, n_rows, n_cols
+            ),
+            MatrixError::ShapeMismatch { expected, found } => write!(
+                f,
+                "expected a matrix of {} rows and {} columns, found {} rows and {} columns",
+                expected.0, expected.1, found.0, found.1
+            ),
+            MatrixError::RaggedRow { row, len, n_cols } => write!(
+                f,
+                "row {} has {} cells where the previous rows have {}",
+                row, len, n_cols
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MatrixError {}
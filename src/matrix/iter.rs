@@ -0,0 +1,243 @@
+//! Borrowing and mutable iterators over the cells, rows and columns of a `Matrix`
+//!
+//! Rows are slices since data is stored in row major order, columns are strided iterators.
+//! Cells are identified by their (row, col) coordinates, as with indexing.
+
+use super::{check_index, Matrix};
+use std::iter::StepBy;
+use std::slice::{Iter, IterMut};
+
+#[cfg(test)]
+mod iter_tests {
+    use super::Connectivity;
+    use crate::matrix::fixtures::numbered;
+    use crate::matrix::Matrix;
+    #[test]
+    fn rows_and_cols() {
+        let m = numbered(3, 3);
+        assert_eq!(
+            vec![vec![0, 1, 2], vec![10, 11, 12], vec![20, 21, 22]],
+            m.rows().map(|r| r.to_vec()).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![vec![0, 10, 20], vec![1, 11, 21], vec![2, 12, 22]],
+            m.cols()
+                .map(|c| c.cloned().collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(&[10, 11, 12], m.row(1));
+        assert_eq!(vec![2, 12, 22], m.col(2).cloned().collect::<Vec<_>>());
+        assert_eq!(0, Matrix::<u8>::from_rows(vec![]).unwrap().rows().count());
+    }
+    #[test]
+    fn rows_without_columns() {
+        let mut m = Matrix::<u8>::filled(3, 0, 0);
+        assert_eq!(
+            vec![0, 0, 0],
+            m.rows().map(|row| row.len()).collect::<Vec<_>>()
+        );
+        assert_eq!(3, m.rows_mut().filter(|row| row.is_empty()).count());
+    }
+    #[test]
+    #[should_panic(expected = "column 3 is out of bounds for a matrix of 3 columns")]
+    fn col_out_of_bounds() {
+        let _ = numbered(3, 3).col(3);
+    }
+    #[test]
+    fn iter_and_indexed_iter() {
+        let mut m = numbered(3, 3);
+        assert_eq!(9, m.iter().count());
+        assert_eq!(Some(((2, 1), &21)), m.indexed_iter().nth(7));
+        for x in m.iter_mut() {
+            *x += 1;
+        }
+        for ((r, c), x) in m.indexed_iter_mut() {
+            *x -= r + c;
+        }
+        assert_eq!(&[11 - 1, 12 - 2, 13 - 3], m.row(1));
+    }
+    #[test]
+    fn mutable_rows_and_cols() {
+        let mut m = numbered(3, 3);
+        m.row_mut(0).reverse();
+        for x in m.col_mut(0) {
+            *x = 0;
+        }
+        for row in m.rows_mut() {
+            row[2] = 9;
+        }
+        assert_eq!(
+            vec![vec![0, 1, 9], vec![0, 11, 9], vec![0, 21, 9]],
+            m.rows().map(|r| r.to_vec()).collect::<Vec<_>>()
+        );
+    }
+    #[test]
+    fn neighbors() {
+        let m = numbered(3, 3);
+        let values = |r, c, connectivity| {
+            m.neighbors(r, c, connectivity)
+                .map(|(_, x)| *x)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(vec![1, 10, 12, 21], values(1, 1, Connectivity::Four));
+        assert_eq!(
+            vec![0, 1, 2, 10, 12, 20, 21, 22],
+            values(1, 1, Connectivity::Eight)
+        );
+        assert_eq!(vec![1, 10], values(0, 0, Connectivity::Four));
+        assert_eq!(vec![11, 12, 21], values(2, 2, Connectivity::Eight));
+        assert_eq!(
+            vec![(0, 1), (1, 0)],
+            m.neighbors(0, 0, Connectivity::Four)
+                .map(|(pos, _)| pos)
+                .collect::<Vec<_>>()
+        );
+    }
+}
+
+/// Which cells count as neighbors of a cell
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Connectivity {
+    /// The cells above, below, left and right
+    Four,
+    /// The cells above, below, left and right plus the diagonals
+    Eight,
+}
+impl Connectivity {
+    /// (row, col) offsets of the neighbors, in row major order
+    fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Connectivity::Four => &[(-1, 0), (0, -1), (0, 1), (1, 0)],
+            Connectivity::Eight => &[
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, -1),
+                (0, 1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+            ],
+        }
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Clone,
+{
+    /// Iterate over the cells in row major order
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.cells.iter()
+    }
+    /// Mutably iterate over the cells in row major order
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+    /// Iterate over the cells along with their (row, col) coordinates
+    ///
+    /// # Example: finding the empty tiles of a 2048 board
+    /// ```
+    /// let board = matrix_display::matrix::Matrix::from([[2, 0], [0, 4]]);
+    /// let empty = board.indexed_iter().filter(|(_, &x)| x == 0).map(|(pos, _)| pos).collect::<Vec<_>>();
+    /// assert_eq!(vec![(0, 1), (1, 0)], empty);
+    /// ```
+    pub fn indexed_iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let n_cols = self.n_cols;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, x)| ((i / n_cols, i % n_cols), x))
+    }
+    /// Mutably iterate over the cells along with their (row, col) coordinates
+    pub fn indexed_iter_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut T)> {
+        let n_cols = self.n_cols;
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, x)| ((i / n_cols, i % n_cols), x))
+    }
+    /// Iterate over the rows, each given as a slice
+    ///
+    /// A matrix without columns still has rows: they are empty slices.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        (0..self.n_rows).map(move |i| self.row(i))
+    }
+    /// Mutably iterate over the rows, each given as a slice
+    pub fn rows_mut(&mut self) -> impl ExactSizeIterator<Item = &mut [T]> {
+        let n_cols = self.n_cols;
+        let mut rest = &mut self.cells[..];
+        (0..self.n_rows).map(move |_| {
+            let (row, tail) = std::mem::take(&mut rest).split_at_mut(n_cols);
+            rest = tail;
+            row
+        })
+    }
+    /// Iterate over the columns, each given as an iterator over its cells from top to bottom
+    pub fn cols(&self) -> impl Iterator<Item = StepBy<Iter<'_, T>>> {
+        (0..self.n_cols).map(move |j| self.col(j))
+    }
+    /// The row at the given index, as a slice
+    ///
+    /// # Panics
+    /// When the row is out of bounds
+    pub fn row(&self, i: usize) -> &[T] {
+        let n_cols = self.n_cols;
+        &self.cells[i * n_cols..(i + 1) * n_cols]
+    }
+    /// The row at the given index, as a mutable slice
+    ///
+    /// # Panics
+    /// When the row is out of bounds
+    pub fn row_mut(&mut self, i: usize) -> &mut [T] {
+        let n_cols = self.n_cols;
+        &mut self.cells[i * n_cols..(i + 1) * n_cols]
+    }
+    /// Iterate over the cells of the column at the given index, from top to bottom
+    ///
+    /// # Panics
+    /// When the column is out of bounds
+    pub fn col(&self, j: usize) -> StepBy<Iter<'_, T>> {
+        check_index("column", j, self.n_cols);
+        self.cells[j..].iter().step_by(self.n_cols)
+    }
+    /// Mutably iterate over the cells of the column at the given index, from top to bottom
+    ///
+    /// # Panics
+    /// When the column is out of bounds
+    pub fn col_mut(&mut self, j: usize) -> StepBy<IterMut<'_, T>> {
+        check_index("column", j, self.n_cols);
+        let n_cols = self.n_cols;
+        self.cells[j..].iter_mut().step_by(n_cols)
+    }
+    /// Iterate over the neighbors of the cell at (row, col) that lie within the matrix,
+    /// along with their (row, col) coordinates, in row major order
+    ///
+    /// # Example: counting live neighbors in the Game of Life
+    /// ```
+    /// use matrix_display::matrix::{Connectivity, Matrix};
+    /// let life = Matrix::from([[true, true, false], [false, false, false], [false, true, false]]);
+    /// let alive = life.neighbors(1, 1, Connectivity::Eight).filter(|(_, &x)| x).count();
+    /// assert_eq!(3, alive);
+    /// ```
+    pub fn neighbors(
+        &self,
+        row: usize,
+        col: usize,
+        connectivity: Connectivity,
+    ) -> impl Iterator<Item = ((usize, usize), &T)> {
+        connectivity.offsets().iter().filter_map(move |&(dr, dc)| {
+            let r = offset(row, dr)?;
+            let c = offset(col, dc)?;
+            self.get(r, c).map(|x| ((r, c), x))
+        })
+    }
+}
+
+fn offset(x: usize, by: isize) -> Option<usize> {
+    if by < 0 {
+        x.checked_sub(by.unsigned_abs())
+    } else {
+        x.checked_add(by as usize)
+    }
+}
@@ -0,0 +1,96 @@
+//! Build a new `Matrix` from the cells of existing ones
+//!
+//! Typically used to turn game state, such as a `Matrix<u32>`, into the `Matrix<Cell<_>>` to display.
+
+use super::Matrix;
+
+#[cfg(test)]
+mod map_tests {
+    use crate::cell::Cell;
+    use crate::matrix::Matrix;
+    #[test]
+    fn map() {
+        let m = Matrix::from([[1, 2], [3, 4]]);
+        let doubled = m.map(|x| 2 * x);
+        assert_eq!(
+            vec![2, 4, 6, 8],
+            doubled.iter().cloned().collect::<Vec<_>>()
+        );
+        assert_eq!((2, 2), (doubled.n_rows(), doubled.n_cols()));
+    }
+    #[test]
+    fn map_indexed() {
+        let m = Matrix::from([[1, 2], [3, 4]]);
+        let cells = m.map_indexed(|r, c, &x| Cell::new(x, 7, ((r + c) % 2) as u8));
+        assert_eq!(Cell::new(2, 7, 1), cells[(0, 1)]);
+        assert_eq!(Cell::new(4, 7, 0), cells[(1, 1)]);
+    }
+    #[test]
+    fn zip_with() {
+        let values = Matrix::from([[1, 2], [3, 4]]);
+        let selected = Matrix::from([[false, true], [false, false]]);
+        let cells = values.zip_with(&selected, |&x, &s| Cell::new(x, 7, if s { 4 } else { 0 }));
+        assert_eq!(Cell::new(2, 7, 4), cells[(0, 1)]);
+        assert_eq!(Cell::new(3, 7, 0), cells[(1, 0)]);
+    }
+    #[test]
+    #[should_panic(expected = "can't zip a matrix of 2x2 cells with a matrix of 1x2 cells")]
+    fn zip_with_checks_shapes() {
+        let a = Matrix::from([[1, 2], [3, 4]]);
+        let b = Matrix::from([[1, 2]]);
+        a.zip_with(&b, |x, y| x + y);
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Clone,
+{
+    /// A matrix of the same shape whose cells are `f` applied to each cell of this one
+    pub fn map<U, F>(&self, mut f: F) -> Matrix<U>
+    where
+        U: Clone,
+        F: FnMut(&T) -> U,
+    {
+        self.map_indexed(|_, _, x| f(x))
+    }
+    /// A matrix of the same shape, ragged last row included, whose cells are `f(row, col, cell)` for each cell of this one
+    ///
+    /// # Example: styling a 2048 board
+    /// ```
+    /// use matrix_display::*;
+    /// let board = matrix::Matrix::from([[2, 0], [4, 2048]]);
+    /// let cells = board.map_indexed(|_, _, &x| cell::Cell::new(x, 0, if x == 0 { 7 } else { 3 }));
+    /// ```
+    pub fn map_indexed<U, F>(&self, mut f: F) -> Matrix<U>
+    where
+        U: Clone,
+        F: FnMut(usize, usize, &T) -> U,
+    {
+        let mut mapped =
+            Matrix::from_fn(self.n_rows(), self.n_cols(), |r, c| f(r, c, &self[(r, c)]));
+        mapped.hidden = self.hidden;
+        mapped
+    }
+    /// A matrix of the same shape whose cells are `f` applied to the cells of both matrices at the same position
+    ///
+    /// # Panics
+    /// When the matrices don't have the same shape
+    pub fn zip_with<U, V, F>(&self, other: &Matrix<U>, mut f: F) -> Matrix<V>
+    where
+        U: Clone,
+        V: Clone,
+        F: FnMut(&T, &U) -> V,
+    {
+        if (self.n_rows(), self.n_cols()) != (other.n_rows(), other.n_cols()) {
+            panic!(
+                "can't zip a matrix of {}x{} cells with a matrix of {}x{} cells",
+                self.n_rows(),
+                self.n_cols(),
+                other.n_rows(),
+                other.n_cols()
+            );
+        }
+        self.map_indexed(|r, c, x| f(x, &other[(r, c)]))
+    }
+}
@@ -1,10 +1,29 @@
 //! Provides `Matrix` which stores a matrix of arbitrary data
 //!
-//! Data is stored as a vector in row major order and a usize representing the number of columns
-//! Data can be accessed for reading through the `enumerate_cells` function
-//! That function returns a vector of a clone of each cell and its `Position`
+//! Data is stored as a vector in row major order along with the number of rows and columns
+//! Single cells are accessed by (row, col) through `get`, `get_mut` or indexing: `matrix[(row, col)]`
+//! The `enumerate_cells` function returns a vector of a clone of each cell and its `Position`
+mod error;
+mod iter;
+mod map;
+#[cfg(feature = "nalgebra")]
+mod nalgebra_interop;
+#[cfg(feature = "ndarray")]
+mod ndarray_interop;
 pub mod position;
+#[cfg(feature = "serde")]
+mod serialization;
+mod shape;
+mod transform;
+mod view;
+pub use self::error::MatrixError;
+pub use self::iter::Connectivity;
 use self::position::Position;
+pub use self::view::{MatrixView, MatrixViewMut};
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::{Index, IndexMut};
+use unicode_width::UnicodeWidthStr;
 
 #[cfg(test)]
 mod matrix_tests {
@@ -29,7 +48,7 @@ mod matrix_tests {
         // 6,  7,  8,
         //
         let n = 3;
-        let v = (0..11)
+        let v = (0..9)
             .map(|_| Cell::new(' ', AnsiColor::default().fg, AnsiColor::default().bg))
             .collect::<Vec<_>>();
         let m = Matrix::new(n, v.clone());
@@ -92,42 +111,397 @@ mod matrix_tests {
         assert_eq!(Position::Bottom, m.from_index(10));
         assert_eq!(Position::BottomRight, m.from_index(11));
     }
+    #[test]
+    fn try_new() {
+        use super::MatrixError;
+        assert_eq!(3, Matrix::try_new(2, vec![0; 6]).unwrap().n_rows());
+        assert_eq!(
+            Err(MatrixError::IncompleteRow { len: 7, n_cols: 2 }),
+            Matrix::try_new(2, vec![0; 7]).map(|m| m.n_rows())
+        );
+        assert_eq!(
+            Err(MatrixError::ZeroColumns),
+            Matrix::try_new(0, vec![0; 7]).map(|m| m.n_rows())
+        );
+        let empty = Matrix::<u8>::try_new(0, Vec::new()).unwrap();
+        assert_eq!((0, 0), (empty.n_rows(), empty.n_cols()));
+    }
+    #[test]
+    #[should_panic(expected = "11 cells can't be split into rows of 3 columns")]
+    fn new_panics_on_incomplete_row() {
+        Matrix::new(3, vec![0; 11]);
+    }
+    #[test]
+    fn from_rows() {
+        use super::MatrixError;
+        let m = Matrix::from_rows(vec![vec![1, 2], vec![3, 4], vec![5, 6]]).unwrap();
+        assert_eq!((3, 2), (m.n_rows(), m.n_cols()));
+        assert_eq!(m.cells, vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(
+            Err(MatrixError::RaggedRow {
+                row: 1,
+                len: 3,
+                n_cols: 2
+            }),
+            Matrix::from_rows(vec![vec![1, 2], vec![3, 4, 5]]).map(|m| m.n_rows())
+        );
+        let empty = Matrix::<u8>::from_rows(Vec::new()).unwrap();
+        assert_eq!((0, 0), (empty.n_rows(), empty.n_cols()));
+        let m = Matrix::<u8>::from_rows(vec![vec![], vec![]]).unwrap();
+        assert_eq!((2, 0), (m.n_rows(), m.n_cols()));
+    }
+    #[test]
+    fn from_fn() {
+        let m = Matrix::from_fn(2, 3, |r, c| 10 * r + c);
+        assert_eq!((2, 3), (m.n_rows(), m.n_cols()));
+        assert_eq!(m.cells, vec![0, 1, 2, 10, 11, 12]);
+    }
+    #[test]
+    fn zero_width_matrices_keep_their_rows() {
+        let m = Matrix::from_fn(3, 0, |r, c| r + c);
+        assert_eq!((3, 0), (m.n_rows(), m.n_cols()));
+        let m = Matrix::<u8>::from([[], []]);
+        assert_eq!((2, 0), (m.n_rows(), m.n_cols()));
+        let mut m = Matrix::from([[1], [2]]);
+        m.remove_col(0);
+        assert_eq!((2, 0), (m.n_rows(), m.n_cols()));
+        m.push_col(vec![3, 4]);
+        assert_eq!(Matrix::from([[3], [4]]), m);
+        m.transpose();
+        m.remove_row(0);
+        assert_eq!((0, 2), (m.n_rows(), m.n_cols()));
+        assert_ne!(Matrix::<u8>::filled(3, 0, 0), Matrix::filled(2, 0, 0));
+    }
+    #[test]
+    fn filled() {
+        let m = Matrix::filled(2, 3, 'x');
+        assert_eq!((2, 3), (m.n_rows(), m.n_cols()));
+        assert_eq!(m.cells, vec!['x'; 6]);
+    }
+    #[test]
+    fn from_iterator() {
+        let m = (0..3).map(|r| vec![r; 2]).collect::<Matrix<_>>();
+        assert_eq!((3, 2), (m.n_rows(), m.n_cols()));
+        assert_eq!(m.cells, vec![0, 0, 1, 1, 2, 2]);
+        let m = (0..3).map(|_| Vec::<u8>::new()).collect::<Matrix<_>>();
+        assert_eq!((3, 0), (m.n_rows(), m.n_cols()));
+    }
+    #[test]
+    #[should_panic(expected = "row 2 has 1 cells where the previous rows have 2")]
+    fn from_iterator_panics_on_ragged_rows() {
+        let _ = vec![vec![1, 2], vec![3, 4], vec![5]]
+            .into_iter()
+            .collect::<Matrix<_>>();
+    }
+    #[test]
+    fn from_array() {
+        let m = Matrix::from([[1, 2, 3], [4, 5, 6]]);
+        assert_eq!((2, 3), (m.n_rows(), m.n_cols()));
+        assert_eq!(m.cells, vec![1, 2, 3, 4, 5, 6]);
+    }
+    #[test]
+    fn get() {
+        let mut m = Matrix::from_fn(2, 3, |r, c| 10 * r + c);
+        assert_eq!(Some(&12), m.get(1, 2));
+        assert_eq!(None, m.get(2, 0));
+        assert_eq!(None, m.get(0, 3));
+        *m.get_mut(0, 1).unwrap() = 42;
+        assert_eq!(Some(&42), m.get(0, 1));
+        assert_eq!(None, m.get_mut(0, 3));
+    }
+    #[test]
+    fn index() {
+        let mut m = Matrix::from_fn(2, 3, |r, c| 10 * r + c);
+        assert_eq!(12, m[(1, 2)]);
+        m[(1, 0)] = 42;
+        assert_eq!(42, m[(1, 0)]);
+        // `at` takes (x, y), i.e. (column, row)
+        assert_eq!(12, *m.at((2, 1)));
+    }
+    #[test]
+    #[should_panic(
+        expected = "cell (row 0, col 3) is out of bounds for a matrix of 2 rows and 3 columns"
+    )]
+    fn index_checks_columns() {
+        let m = Matrix::from_fn(2, 3, |r, c| 10 * r + c);
+        let _ = m[(0, 3)];
+    }
+    #[test]
+    #[should_panic(expected = "cell (row 3, col 0) is out of bounds")]
+    fn at_checks_both_axes() {
+        let mut m = Matrix::from_fn(2, 3, |r, c| 10 * r + c);
+        m.at((0, 3));
+    }
+    #[test]
+    fn eq_and_hash() {
+        use std::collections::HashSet;
+        let a = Matrix::from([[1, 2], [3, 4]]);
+        assert_eq!(a, Matrix::new(2, vec![1, 2, 3, 4]));
+        assert_ne!(a, Matrix::new(4, vec![1, 2, 3, 4]));
+        assert_ne!(a, Matrix::from([[1, 2], [3, 5]]));
+        let mut seen = HashSet::new();
+        assert!(seen.insert(a.clone()));
+        assert!(!seen.insert(a.rotated_cw().rotated_ccw()));
+        assert!(seen.insert(a.rotated_cw()));
+    }
+    #[test]
+    fn debug_is_a_grid() {
+        let wide = Matrix::from([["中文", "a"], ["b", "cc"]]);
+        assert_eq!(
+            "Matrix 2x2 [\n    [\"中文\",  \"a\"],\n    [   \"b\", \"cc\"],\n]",
+            format!("{:?}", wide)
+        );
+        let m = Matrix::from([['a', 'b'], ['c', 'd']]);
+        assert_eq!(
+            "Matrix 2x2 [\n    ['a', 'b'],\n    ['c', 'd'],\n]",
+            format!("{:?}", m)
+        );
+        let empty = Matrix::<u8>::from_rows(vec![]).unwrap();
+        assert_eq!("Matrix 0x0 []", format!("{:?}", empty));
+        assert_eq!(
+            "Matrix 3x0 []",
+            format!("{:?}", Matrix::<u8>::filled(3, 0, 0))
+        );
+    }
+    #[test]
+    fn ragged() {
+        let m = Matrix::ragged(3, vec![1, 2, 3, 4], 0).unwrap();
+        assert_eq!(m.cells, vec![1, 2, 3, 4, 0, 0]);
+        assert_eq!(1, m.last_row_len());
+        assert_ne!(m, Matrix::padded(3, vec![1, 2, 3, 4], 0).unwrap());
+        assert_eq!(1, m.map(|x| x + 1).last_row_len());
+        let t = m.transposed();
+        assert_eq!(t.n_cols(), t.last_row_len());
+        let m = Matrix::ragged(2, vec![1, 2], 0).unwrap();
+        assert_eq!(2, m.last_row_len());
+    }
+    #[test]
+    fn padded() {
+        let m = Matrix::padded(3, vec![1, 2, 3, 4], 0).unwrap();
+        assert_eq!(m.cells, vec![1, 2, 3, 4, 0, 0]);
+        let m = Matrix::padded(2, vec![1, 2], 0).unwrap();
+        assert_eq!(m.cells, vec![1, 2]);
+        assert!(Matrix::padded(0, vec![1, 2], 0).is_err());
+    }
+}
+
+/// Matrices and helpers shared by the tests of the matrix modules
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::Matrix;
+    /// A matrix whose cells tell their coordinates: 10 * row + col, e.g. 12 for (1, 2)
+    pub(crate) fn numbered(n_rows: usize, n_cols: usize) -> Matrix<usize> {
+        Matrix::from_fn(n_rows, n_cols, |r, c| 10 * r + c)
+    }
+    /// The rows of a matrix as vectors, to compare them all at once
+    pub(crate) fn rows<T: Clone>(m: &Matrix<T>) -> Vec<Vec<T>> {
+        m.rows().map(|r| r.to_vec()).collect::<Vec<_>>()
+    }
 }
 
 /// Stores a matrix of arbitrary data
 ///
 /// Gives mutable access to cell at given position
 /// Gives owned clone of data with position of each cell within the matrix
-#[derive(Clone)]
+///
+/// Matrices compare and hash by shape, content and ragged last row (see `ragged`).
+/// Their `Debug` output is laid out as a grid.
+/// With the `serde` feature, they serialize as their number of rows and columns plus their cells in row major order.
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Matrix<T>
 where
     T: Clone,
 {
+    n_rows: usize,
     n_cols: usize,
     cells: Vec<T>,
+    /// Number of filler cells completing a ragged last row, which aren't displayed
+    hidden: usize,
 }
 impl<T> Matrix<T>
 where
     T: Clone,
 {
     /// Construct a matrix with the number of columns and the data represented as a row-major ordered `Vec`
+    ///
+    /// # Panics
+    /// When the number of cells isn't a multiple of the number of columns (see `try_new`, or `padded`
+    /// to complete the last row). Up to version 1.0, the incomplete last row was silently left out instead.
     pub fn new(n_cols: usize, cells: Vec<T>) -> Matrix<T> {
+        match Matrix::try_new(n_cols, cells) {
+            Ok(m) => m,
+            Err(e) => panic!("{}", e),
+        }
+    }
+    /// Construct a matrix with the number of columns and the data represented as a row-major ordered `Vec`
+    ///
+    /// Fails when the number of cells isn't a multiple of the number of columns,
+    /// or when cells are given for 0 columns. An empty `Vec` makes an empty matrix.
+    pub fn try_new(n_cols: usize, cells: Vec<T>) -> Result<Matrix<T>, MatrixError> {
+        if n_cols == 0 && !cells.is_empty() {
+            Err(MatrixError::ZeroColumns)
+        } else if n_cols != 0 && cells.len() % n_cols != 0 {
+            Err(MatrixError::IncompleteRow {
+                len: cells.len(),
+                n_cols,
+            })
+        } else {
+            let n_rows = cells.len().checked_div(n_cols).unwrap_or(0);
+            Ok(Matrix {
+                n_rows,
+                n_cols,
+                cells,
+                hidden: 0,
+            })
+        }
+    }
+    /// Construct a matrix like `try_new`, completing an incomplete last row with copies of `filler`
+    ///
+    /// The filler cells are displayed like any other cell: see `ragged` to display a shorter last row instead.
+    ///
+    /// # Example:
+    /// ```
+    /// let m = matrix_display::matrix::Matrix::padded(3, vec![1, 2, 3, 4], 0).unwrap();
+    /// assert_eq!(2, m.n_rows());
+    /// ```
+    pub fn padded(n_cols: usize, mut cells: Vec<T>, filler: T) -> Result<Matrix<T>, MatrixError> {
+        if n_cols != 0 {
+            let missing = (n_cols - cells.len() % n_cols) % n_cols;
+            cells.extend(std::iter::repeat(filler).take(missing));
+        }
+        Matrix::try_new(n_cols, cells)
+    }
+    /// Construct a matrix like `padded`, displaying the incomplete last row as a shorter, ragged row
+    ///
+    /// The filler cells are stored and can be accessed like any other cell, but they aren't displayed:
+    /// the bottom border follows the actual length of the last row.
+    /// Changes that move cells to other rows or columns, or add cells after the last row,
+    /// display the filler cells again: transposing, rotating, flipping, rolling, swapping, resizing,
+    /// adding or removing a row at the bottom, and adding or removing a column.
+    ///
+    /// # Example: 10 items laid out in rows of 4
+    /// ```
+    /// let m = matrix_display::matrix::Matrix::ragged(4, (0..10).collect(), 0).unwrap();
+    /// assert_eq!((3, 4), (m.n_rows(), m.n_cols()));
+    /// assert_eq!(2, m.last_row_len());
+    /// ```
+    pub fn ragged(n_cols: usize, cells: Vec<T>, filler: T) -> Result<Matrix<T>, MatrixError> {
+        let len = cells.len();
+        let mut m = Matrix::padded(n_cols, cells, filler)?;
+        m.hidden = m.cells.len() - len;
+        Ok(m)
+    }
+    /// Construct a matrix from its rows
+    ///
+    /// Fails when the rows don't all have the same length.
+    ///
+    /// # Example:
+    /// ```
+    /// use matrix_display::matrix::Matrix;
+    /// let m = Matrix::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+    /// assert_eq!((2, 3), (m.n_rows(), m.n_cols()));
+    /// assert!(Matrix::from_rows(vec![vec![1, 2, 3], vec![4, 5]]).is_err());
+    /// ```
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Matrix<T>, MatrixError> {
+        let n_rows = rows.len();
+        let n_cols = rows.first().map(|row| row.len()).unwrap_or(0);
+        let mut cells = Vec::with_capacity(n_cols * rows.len());
+        for (i, row) in rows.into_iter().enumerate() {
+            if row.len() != n_cols {
+                return Err(MatrixError::RaggedRow {
+                    row: i,
+                    len: row.len(),
+                    n_cols,
+                });
+            }
+            cells.extend(row);
+        }
+        // Rows without any cell still count, which the number of cells alone can't tell
+        Ok(Matrix {
+            n_rows,
+            n_cols,
+            cells,
+            hidden: 0,
+        })
+    }
+    /// Construct a matrix of n_rows by n_cols by calling `f(row, col)` for each cell
+    ///
+    /// # Example:
+    /// ```
+    /// // A chess board's colors
+    /// let m = matrix_display::matrix::Matrix::from_fn(8, 8, |r, c| if (r + c) % 2 == 0 { 0 } else { 7 });
+    /// ```
+    pub fn from_fn<F>(n_rows: usize, n_cols: usize, mut f: F) -> Matrix<T>
+    where
+        F: FnMut(usize, usize) -> T,
+    {
+        let cells = (0..n_rows)
+            .flat_map(|r| (0..n_cols).map(move |c| (r, c)))
+            .map(|(r, c)| f(r, c))
+            .collect::<Vec<_>>();
         Matrix {
-            n_cols: n_cols,
-            cells: cells,
+            n_rows,
+            n_cols,
+            cells,
+            hidden: 0,
+        }
+    }
+    /// Construct a matrix of n_rows by n_cols where every cell is a copy of `value`
+    pub fn filled(n_rows: usize, n_cols: usize, value: T) -> Matrix<T> {
+        Matrix {
+            n_rows,
+            n_cols,
+            cells: vec![value; n_rows * n_cols],
+            hidden: 0,
         }
     }
     /// Number of rows in the matrix
     pub fn n_rows(&self) -> usize {
-        self.cells.len() / self.n_cols()
+        self.n_rows
     }
     /// Number of columns in the matrix
     pub fn n_cols(&self) -> usize {
         self.n_cols
     }
-    /// Mutably access element at given position (row index, column index)
+    /// Number of cells displayed in the last row: fewer than `n_cols` when it is ragged (see `ragged`)
+    pub fn last_row_len(&self) -> usize {
+        self.n_cols - self.hidden
+    }
+    /// Access element at given row and column, or None if out of bounds
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        let index = self.index_of(row, col)?;
+        self.cells.get(index)
+    }
+    /// Mutably access element at given row and column, or None if out of bounds
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        let index = self.index_of(row, col)?;
+        self.cells.get_mut(index)
+    }
+    /// Mutably access element at given position (x, y), that is (column index, row index)
+    ///
+    /// The order matches cursor positions and `MatrixDisplay::coordinates_at_cursor_position`.
+    /// Prefer `get_mut` or indexing with `[(row, col)]` otherwise.
+    ///
+    /// # Panics
+    /// When x or y is out of bounds
     pub fn at(&mut self, (x, y): (usize, usize)) -> &mut T {
-        &mut self.cells[x + self.n_cols * y]
+        &mut self[(y, x)]
+    }
+    fn index_of(&self, row: usize, col: usize) -> Option<usize> {
+        if row < self.n_rows() && col < self.n_cols() {
+            Some(col + self.n_cols * row)
+        } else {
+            None
+        }
+    }
+    fn out_of_bounds(&self, (row, col): (usize, usize)) -> ! {
+        panic!(
+            "cell (row {}, col {}) is out of bounds for a matrix of {} rows and {} columns",
+            row,
+            col,
+            self.n_rows(),
+            self.n_cols()
+        )
     }
     fn is_top(&self, index: usize) -> bool {
         index < self.n_cols()
@@ -141,6 +515,7 @@ where
     fn is_bottom(&self, index: usize) -> bool {
         index >= (self.n_rows() - 1) * self.n_cols()
     }
+    #[allow(clippy::wrong_self_convention)]
     fn from_index(&self, index: usize) -> position::Position {
         if self.is_top(index) && self.is_left(index) {
             Position::TopLeft
@@ -175,3 +550,112 @@ where
             .collect::<Vec<_>>()
     }
 }
+
+/// Panic unless index i of a row or column is below len
+fn check_index(what: &str, i: usize, len: usize) {
+    if i >= len {
+        panic!(
+            "{} {} is out of bounds for a matrix of {} {}s",
+            what, i, len, what
+        );
+    }
+}
+
+/// Index a matrix with a (row, col) pair
+///
+/// # Panics
+/// When row or col is out of bounds
+impl<T> Index<(usize, usize)> for Matrix<T>
+where
+    T: Clone,
+{
+    type Output = T;
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        match self.index_of(row, col) {
+            Some(index) => &self.cells[index],
+            None => self.out_of_bounds((row, col)),
+        }
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T>
+where
+    T: Clone,
+{
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        match self.index_of(row, col) {
+            Some(index) => &mut self.cells[index],
+            None => self.out_of_bounds((row, col)),
+        }
+    }
+}
+
+/// Print the matrix as a grid, one row per line, with columns aligned
+///
+/// # Example:
+/// ```
+/// let m = matrix_display::matrix::Matrix::from([[1, 20], [300, 4]]);
+/// assert_eq!("Matrix 2x2 [\n    [  1, 20],\n    [300,  4],\n]", format!("{:?}", m));
+/// ```
+impl<T> fmt::Debug for Matrix<T>
+where
+    T: Clone + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let texts = self.map(|x| format!("{:?}", x));
+        let widths = texts
+            .cols()
+            .map(|col| col.map(|text| text.width()).max().unwrap_or(0))
+            .collect::<Vec<_>>();
+        write!(f, "Matrix {}x{} [", self.n_rows(), self.n_cols())?;
+        // Rows without any cell aren't worth a line each
+        for row in texts.rows().filter(|row| !row.is_empty()) {
+            write!(f, "\n    [")?;
+            for (j, text) in row.iter().enumerate() {
+                if j > 0 {
+                    write!(f, ", ")?;
+                }
+                // Padded by display width rather than by number of chars, for wide characters
+                write!(f, "{}{}", " ".repeat(widths[j] - text.width()), text)?;
+            }
+            write!(f, "],")?;
+        }
+        if !self.cells.is_empty() {
+            writeln!(f)?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// Collect rows into a matrix
+///
+/// # Panics
+/// When the rows don't all have the same length (see `Matrix::from_rows`)
+impl<T> FromIterator<Vec<T>> for Matrix<T>
+where
+    T: Clone,
+{
+    fn from_iter<I: IntoIterator<Item = Vec<T>>>(rows: I) -> Matrix<T> {
+        match Matrix::from_rows(rows.into_iter().collect()) {
+            Ok(m) => m,
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
+
+/// Convert a 2D array, given as an array of rows
+impl<T, const R: usize, const C: usize> From<[[T; C]; R]> for Matrix<T>
+where
+    T: Clone,
+{
+    fn from(rows: [[T; C]; R]) -> Matrix<T> {
+        Matrix {
+            n_rows: R,
+            n_cols: C,
+            cells: IntoIterator::into_iter(rows)
+                .flat_map(IntoIterator::into_iter)
+                .collect(),
+            hidden: 0,
+        }
+    }
+}
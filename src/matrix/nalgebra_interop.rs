@@ -0,0 +1,88 @@
+//! Conversions between `Matrix` and nalgebra's matrices, with the `nalgebra` feature
+//!
+//! nalgebra stores its cells in column major order while `Matrix` stores them in row major order,
+//! so conversions copy the cells, reordering them so that every cell keeps its (row, col) coordinates.
+
+use super::{Matrix, MatrixError};
+use nalgebra::{DMatrix, Dim, RawStorage, SMatrix, Scalar};
+use std::convert::TryFrom;
+
+#[cfg(test)]
+mod nalgebra_interop_tests {
+    use crate::matrix::{Matrix, MatrixError};
+    use nalgebra::{DMatrix, Matrix2x3, Matrix3x2};
+    use std::convert::TryFrom;
+    #[test]
+    fn nalgebra_to_matrix() {
+        let d = DMatrix::from_row_slice(2, 3, &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(
+            Matrix::from([[1, 2, 3], [4, 5, 6]]),
+            Matrix::from(d.clone())
+        );
+        let s = Matrix2x3::new(1, 2, 3, 4, 5, 6);
+        assert_eq!(Matrix::from([[1, 2, 3], [4, 5, 6]]), Matrix::from(s));
+        assert_eq!(
+            Matrix::from([[2, 3], [5, 6]]),
+            Matrix::from(d.columns(1, 2))
+        );
+    }
+    #[test]
+    fn matrix_to_nalgebra() {
+        let m = Matrix::from([[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(
+            DMatrix::from_row_slice(2, 3, &[1, 2, 3, 4, 5, 6]),
+            DMatrix::from(m.clone())
+        );
+        assert_eq!(
+            Ok(Matrix2x3::new(1, 2, 3, 4, 5, 6)),
+            Matrix2x3::try_from(m.clone())
+        );
+        assert_eq!(
+            Err(MatrixError::ShapeMismatch {
+                expected: (3, 2),
+                found: (2, 3)
+            }),
+            Matrix3x2::try_from(m)
+        );
+    }
+}
+
+/// Convert any nalgebra matrix, `DMatrix`, `SMatrix` or a view, copying its cells into row major order
+impl<T, R, C, S> From<nalgebra::Matrix<T, R, C, S>> for Matrix<T>
+where
+    T: Scalar,
+    R: Dim,
+    C: Dim,
+    S: RawStorage<T, R, C>,
+{
+    fn from(m: nalgebra::Matrix<T, R, C, S>) -> Matrix<T> {
+        Matrix::from_fn(m.nrows(), m.ncols(), |r, c| m[(r, c)].clone())
+    }
+}
+
+/// Convert a matrix into a dynamically sized nalgebra matrix, copying its cells into column major order
+impl<T> From<Matrix<T>> for DMatrix<T>
+where
+    T: Scalar,
+{
+    fn from(m: Matrix<T>) -> DMatrix<T> {
+        DMatrix::from_row_iterator(m.n_rows(), m.n_cols(), m.cells)
+    }
+}
+
+/// Convert a matrix into a statically sized nalgebra matrix, failing unless it has R rows and C columns
+impl<T, const R: usize, const C: usize> TryFrom<Matrix<T>> for SMatrix<T, R, C>
+where
+    T: Scalar,
+{
+    type Error = MatrixError;
+    fn try_from(m: Matrix<T>) -> Result<SMatrix<T, R, C>, MatrixError> {
+        if (m.n_rows(), m.n_cols()) != (R, C) {
+            return Err(MatrixError::ShapeMismatch {
+                expected: (R, C),
+                found: (m.n_rows(), m.n_cols()),
+            });
+        }
+        Ok(SMatrix::from_row_iterator(m.cells))
+    }
+}
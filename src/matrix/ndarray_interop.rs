@@ -0,0 +1,158 @@
+//! Conversions between `Matrix` and `ndarray::Array2`, with the `ndarray` feature
+//!
+//! Both store cells in row major order, so converting an array in standard layout
+//! moves its buffer without copying any cell.
+//! `MatrixDisplay::from_array` displays an `ArrayView2` directly, styling its cells on the fly.
+
+use super::Matrix;
+use crate::cell::RenderCell;
+use crate::{Format, GenericMatrixDisplay, Source};
+use ndarray::{Array2, ArrayView2};
+
+#[cfg(test)]
+mod ndarray_interop_tests {
+    use crate::cell::Cell;
+    use crate::matrix::Matrix;
+    use crate::screen::VirtualScreen;
+    use crate::style::BordersStyle;
+    use crate::{Format, MatrixDisplay};
+    use ndarray::{array, s, Array2};
+    #[test]
+    fn array_to_matrix() {
+        let a = array![[1, 2, 3], [4, 5, 6]];
+        assert_eq!(
+            Matrix::from([[1, 2, 3], [4, 5, 6]]),
+            Matrix::from(a.clone())
+        );
+        assert_eq!(
+            Matrix::from([[1, 4], [2, 5], [3, 6]]),
+            Matrix::from(a.t().to_owned())
+        );
+        let mut sliced = a.clone();
+        sliced.slice_collapse(s![1.., 1..]);
+        assert_eq!(Matrix::from([[5, 6]]), Matrix::from(sliced));
+        assert_eq!(
+            Matrix::from([[2], [5]]),
+            Matrix::from(a.slice(s![.., 1..2]))
+        );
+    }
+    #[test]
+    fn matrix_to_array() {
+        let m = Matrix::from([[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(ndarray::array![[1, 2, 3], [4, 5, 6]], Array2::from(m));
+        let empty = Matrix::<u8>::from_rows(vec![]).unwrap();
+        assert_eq!((0, 0), Array2::from(empty).dim());
+    }
+    #[test]
+    fn display_array_view() {
+        let f = Format::new(1, 1);
+        let a = ndarray::array![[1.0, -2.0], [0.5, 3.0]];
+        let d = MatrixDisplay::from_array(&f, a.view(), |_, _, &x: &f64| {
+            Cell::new(if x < 0.0 { '-' } else { '+' }, 7, 0)
+        });
+        let mut s = VirtualScreen::new();
+        d.print(&mut s, &BordersStyle::None);
+        assert_eq!(vec!["+-", "++"], s.lines());
+    }
+    #[test]
+    fn hit_test_array_view() {
+        let f = Format::new(2, 1);
+        let a = ndarray::array![[1, 2, 3], [4, 5, 6]];
+        let mut d = MatrixDisplay::from_array(&f, a.view(), |_, _, &x: &i32| Cell::new(x, 7, 0));
+        assert_eq!((2, 1), d.coordinates_at_cursor_position((6, 2)));
+        assert!(d.try_cell_at_cursor_position((6, 2)).is_none());
+    }
+}
+
+/// Convert an owned array, moving its buffer when it is in standard (row major) layout
+impl<T> From<Array2<T>> for Matrix<T>
+where
+    T: Clone,
+{
+    fn from(array: Array2<T>) -> Matrix<T> {
+        let (n_rows, n_cols) = array.dim();
+        let cells = if array.is_standard_layout() {
+            // The cells are contiguous, but an array sliced in place may not start or end with its buffer
+            let (mut cells, offset) = array.into_raw_vec_and_offset();
+            let offset = offset.unwrap_or(0);
+            cells.truncate(offset + n_rows * n_cols);
+            cells.drain(..offset);
+            cells
+        } else {
+            array.iter().cloned().collect::<Vec<_>>()
+        };
+        Matrix {
+            n_rows,
+            n_cols,
+            cells,
+            hidden: 0,
+        }
+    }
+}
+
+/// Copy the cells of an array view
+impl<'a, T> From<ArrayView2<'a, T>> for Matrix<T>
+where
+    T: Clone,
+{
+    fn from(view: ArrayView2<'a, T>) -> Matrix<T> {
+        Matrix {
+            n_rows: view.nrows(),
+            n_cols: view.ncols(),
+            cells: view.iter().cloned().collect::<Vec<_>>(),
+            hidden: 0,
+        }
+    }
+}
+
+/// Convert a matrix into an array in standard layout, moving its cells without copying them
+impl<T> From<Matrix<T>> for Array2<T>
+where
+    T: Clone,
+{
+    fn from(matrix: Matrix<T>) -> Array2<T> {
+        let shape = (matrix.n_rows(), matrix.n_cols());
+        Array2::from_shape_vec(shape, matrix.cells)
+            .expect("a matrix always has n_rows * n_cols cells")
+    }
+}
+
+impl<'a, C> GenericMatrixDisplay<'a, C>
+where
+    C: RenderCell + Clone + 'a,
+{
+    /// Construct a display of an ndarray view, styled on the fly by `style(row, col, value)`
+    ///
+    /// Like `MatrixDisplay::styled`, cells are only styled when rendered and can't be modified
+    /// through `try_cell_at_cursor_position`, which returns None.
+    ///
+    /// # Example: printing the sign of each value of an array
+    /// ```
+    /// use matrix_display::*;
+    /// let format = Format::new(5, 1);
+    /// let a = ndarray::array![[1.5, -2.0], [0.0, 3.25]];
+    /// let display = MatrixDisplay::from_array(&format, a.view(), |_, _, &x: &f64| {
+    ///     cell::Cell::new(x, 7, if x < 0.0 { 1 } else { 0 })
+    /// });
+    /// display.print(&mut std::io::stdout(), &style::BordersStyle::Light);
+    /// ```
+    pub fn from_array<D, F>(
+        f: &'a Format,
+        array: ArrayView2<'a, D>,
+        style: F,
+    ) -> GenericMatrixDisplay<'a, C>
+    where
+        F: Fn(usize, usize, &D) -> C + 'a,
+    {
+        let (n_rows, n_cols) = array.dim();
+        GenericMatrixDisplay::with_source(
+            f,
+            Source::Styled {
+                n_rows,
+                n_cols,
+                last_row_len: n_cols,
+                style: Box::new(move |r, c| style(r, c, &array[[r, c]])),
+            },
+        )
+    }
+}
@@ -1,6 +1,7 @@
 //! A simple human readable way of defining a position within a box
 pub use self::position::Position;
 
+#[allow(clippy::module_inception)]
 mod position {
     /// Enum that describes a position within a rectangle
     ///
@@ -8,7 +9,7 @@ mod position {
     /// The values of the enum denote the exact position (i.e: `BottomLeft`)
     /// Helper functions help determine wether that position is part of a given border
     /// (i.e: `BottomLeft` is both part of `bottom()` and `left()`)
-    #[derive(Debug, PartialEq)]
+    #[derive(Clone, Copy, Debug, PartialEq)]
     pub enum Position {
         Top,
         Left,
@@ -22,41 +23,51 @@ mod position {
     }
 
     impl Position {
+        /// The position of the cell at (row, col) within a grid of n_rows by n_cols cells
+        pub fn within((row, col): (usize, usize), (n_rows, n_cols): (usize, usize)) -> Position {
+            let top = row == 0;
+            let left = col == 0;
+            let right = col + 1 >= n_cols;
+            let bottom = row + 1 >= n_rows;
+            match (top, left, right, bottom) {
+                (true, true, _, _) => Position::TopLeft,
+                (true, _, true, _) => Position::TopRight,
+                (_, true, _, true) => Position::BottomLeft,
+                (_, _, true, true) => Position::BottomRight,
+                (true, _, _, _) => Position::Top,
+                (_, true, _, _) => Position::Left,
+                (_, _, true, _) => Position::Right,
+                (_, _, _, true) => Position::Bottom,
+                _ => Position::Middle,
+            }
+        }
         /// Is it part of the top row?
         pub fn top(&self) -> bool {
-            match *self {
-                Position::TopLeft => true,
-                Position::Top => true,
-                Position::TopRight => true,
-                _ => false,
-            }
+            matches!(
+                *self,
+                Position::TopLeft | Position::Top | Position::TopRight
+            )
         }
         /// Is it part of the leftmost column?
         pub fn left(&self) -> bool {
-            match *self {
-                Position::TopLeft => true,
-                Position::Left => true,
-                Position::BottomLeft => true,
-                _ => false,
-            }
+            matches!(
+                *self,
+                Position::TopLeft | Position::Left | Position::BottomLeft
+            )
         }
         /// Is it part of the rightmost column?
         pub fn right(&self) -> bool {
-            match *self {
-                Position::TopRight => true,
-                Position::Right => true,
-                Position::BottomRight => true,
-                _ => false,
-            }
+            matches!(
+                *self,
+                Position::TopRight | Position::Right | Position::BottomRight
+            )
         }
         /// Is it part of the bottom row?
         pub fn bottom(&self) -> bool {
-            match *self {
-                Position::BottomLeft => true,
-                Position::Bottom => true,
-                Position::BottomRight => true,
-                _ => false,
-            }
+            matches!(
+                *self,
+                Position::BottomLeft | Position::Bottom | Position::BottomRight
+            )
         }
     }
 }
@@ -0,0 +1,133 @@
+//! Serialize a `Matrix` as its shape plus its cells in row major order, with the `serde` feature
+//!
+//! A ragged matrix also records how many filler cells complete its last row.
+//! The number of cells is checked against the shape when deserializing.
+
+use super::{Matrix, MatrixError};
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, Serializer};
+
+#[cfg(test)]
+mod serialization_tests {
+    use crate::cell::Cell;
+    use crate::matrix::Matrix;
+    use crate::style::BordersStyle;
+    use crate::Format;
+    #[test]
+    fn matrix_round_trip() {
+        let m = Matrix::from([[1, 2, 3], [4, 5, 6]]);
+        let json = serde_json::to_string(&m).unwrap();
+        assert_eq!(r#"{"n_rows":2,"n_cols":3,"cells":[1,2,3,4,5,6]}"#, json);
+        assert_eq!(m, serde_json::from_str(&json).unwrap());
+        let cells = m.map(|&x| Cell::new(x, 7, 0));
+        let json = serde_json::to_string(&cells).unwrap();
+        assert_eq!(cells, serde_json::from_str(&json).unwrap());
+    }
+    #[test]
+    fn matrix_length_is_validated() {
+        let err = serde_json::from_str::<Matrix<u8>>(r#"{"n_rows":2,"n_cols":3,"cells":[1,2,3]}"#)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("3 cells were given for a matrix of 2 rows and 3 columns"));
+        assert!(
+            serde_json::from_str::<Matrix<u8>>(r#"{"n_rows":1,"n_cols":0,"cells":[1]}"#).is_err()
+        );
+        let json = format!(
+            r#"{{"n_rows":{},"n_cols":2,"cells":[1,2,3,4,5,6]}}"#,
+            usize::MAX
+        );
+        assert!(serde_json::from_str::<Matrix<u8>>(&json).is_err());
+    }
+    #[test]
+    fn ragged_round_trip() {
+        let m = Matrix::ragged(3, vec![1, 2, 3, 4], 0).unwrap();
+        let json = serde_json::to_string(&m).unwrap();
+        assert_eq!(
+            r#"{"n_rows":2,"n_cols":3,"cells":[1,2,3,4,0,0],"hidden":2}"#,
+            json
+        );
+        assert_eq!(m, serde_json::from_str(&json).unwrap());
+        assert!(serde_json::from_str::<Matrix<u8>>(
+            r#"{"n_rows":1,"n_cols":2,"cells":[1,2],"hidden":2}"#
+        )
+        .is_err());
+    }
+    #[test]
+    fn layout_round_trip() {
+        let json = serde_json::to_string(&(Format::new(7, 3), BordersStyle::Double)).unwrap();
+        assert_eq!(r#"[{"cell_w":7,"cell_h":3},"Double"]"#, json);
+        let (format, borders): (Format, BordersStyle) = serde_json::from_str(&json).unwrap();
+        assert_eq!((7, 3), (format.cell_w, format.cell_h));
+        assert_eq!(BordersStyle::Double, borders);
+    }
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename = "Matrix")]
+struct MatrixRef<'a, T> {
+    n_rows: usize,
+    n_cols: usize,
+    cells: &'a [T],
+    #[serde(skip_serializing_if = "is_zero")]
+    hidden: usize,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename = "Matrix")]
+struct MatrixData<T> {
+    n_rows: usize,
+    n_cols: usize,
+    cells: Vec<T>,
+    #[serde(default)]
+    hidden: usize,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+impl<T> Serialize for Matrix<T>
+where
+    T: Clone + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        MatrixRef {
+            n_rows: self.n_rows(),
+            n_cols: self.n_cols(),
+            cells: &self.cells,
+            hidden: self.hidden,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Matrix<T>
+where
+    T: Clone + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Matrix<T>, D::Error> {
+        let data = MatrixData::deserialize(deserializer)?;
+        // The shape comes from untrusted input: a product that overflows can't match any number of cells
+        if data.n_rows.checked_mul(data.n_cols) != Some(data.cells.len()) {
+            return Err(D::Error::custom(MatrixError::WrongLength {
+                len: data.cells.len(),
+                n_rows: data.n_rows,
+                n_cols: data.n_cols,
+            }));
+        }
+        // A whole row of filler cells would be a hidden row
+        if data.hidden > 0 && (data.n_rows == 0 || data.hidden >= data.n_cols) {
+            return Err(D::Error::custom(format!(
+                "{} hidden cells can't complete the last row of a matrix of {} rows and {} columns",
+                data.hidden, data.n_rows, data.n_cols
+            )));
+        }
+        Ok(Matrix {
+            n_rows: data.n_rows,
+            n_cols: data.n_cols,
+            cells: data.cells,
+            hidden: data.hidden,
+        })
+    }
+}
@@ -0,0 +1,258 @@
+//! Change the shape of a `Matrix` at runtime: add and remove rows and columns, resize, transpose and swap
+//!
+//! Cells stay in row major order after every operation, so `Position`s are always
+//! those of the new shape.
+//! Rows and columns given with the wrong number of cells, or indices out of bounds, cause a panic,
+//! as they would with `Vec::insert`.
+
+use super::{check_index, Matrix};
+
+#[cfg(test)]
+mod shape_tests {
+    use crate::matrix::fixtures::{numbered, rows};
+    use crate::matrix::position::Position;
+    use crate::matrix::Matrix;
+    #[test]
+    fn rows_are_added_and_removed() {
+        let mut m = numbered(2, 3);
+        m.push_row(vec![20, 21, 22]);
+        m.insert_row(0, vec![7, 8, 9]);
+        assert_eq!(
+            vec![
+                vec![7, 8, 9],
+                vec![0, 1, 2],
+                vec![10, 11, 12],
+                vec![20, 21, 22]
+            ],
+            rows(&m)
+        );
+        assert_eq!(vec![0, 1, 2], m.remove_row(1));
+        assert_eq!((3, 3), (m.n_rows(), m.n_cols()));
+        let mut empty = Matrix::from_rows(vec![]).unwrap();
+        empty.push_row(vec![1, 2]);
+        assert_eq!(vec![vec![1, 2]], rows(&empty));
+    }
+    #[test]
+    fn cols_are_added_and_removed() {
+        let mut m = numbered(2, 3);
+        m.push_col(vec![3, 13]);
+        m.insert_col(0, vec![9, 19]);
+        assert_eq!(
+            vec![vec![9, 0, 1, 2, 3], vec![19, 10, 11, 12, 13]],
+            rows(&m)
+        );
+        assert_eq!(vec![1, 11], m.remove_col(2));
+        assert_eq!(vec![vec![9, 0, 2, 3], vec![19, 10, 12, 13]], rows(&m));
+        let mut empty = Matrix::from_rows(vec![]).unwrap();
+        empty.push_col(vec![1, 2]);
+        assert_eq!(vec![vec![1], vec![2]], rows(&empty));
+        assert_eq!(vec![1, 2], empty.remove_col(0));
+        assert_eq!((2, 0), (empty.n_rows(), empty.n_cols()));
+    }
+    #[test]
+    #[should_panic(expected = "a row of 2 cells can't be added to a matrix of 3 columns")]
+    fn push_row_checks_length() {
+        numbered(2, 3).push_row(vec![1, 2]);
+    }
+    #[test]
+    #[should_panic(expected = "a column of 3 cells can't be added to a matrix of 2 rows")]
+    fn insert_col_checks_length() {
+        numbered(2, 3).insert_col(1, vec![1, 2, 3]);
+    }
+    #[test]
+    fn positions_follow_the_new_shape() {
+        let mut m = numbered(2, 3);
+        m.push_col(vec![3, 13]);
+        let positions = m
+            .enumerate_cells()
+            .into_iter()
+            .map(|(_, p)| p)
+            .collect::<Vec<_>>();
+        assert_eq!(Position::Top, positions[2]);
+        assert_eq!(Position::TopRight, positions[3]);
+        assert_eq!(Position::BottomLeft, positions[4]);
+        assert_eq!(Position::BottomRight, positions[7]);
+    }
+    #[test]
+    fn resize() {
+        let mut m = numbered(2, 3);
+        m.resize(3, 2, 0);
+        assert_eq!(vec![vec![0, 1], vec![10, 11], vec![0, 0]], rows(&m));
+        m.resize(1, 4, 5);
+        assert_eq!(vec![vec![0, 1, 5, 5]], rows(&m));
+    }
+    #[test]
+    fn transpose() {
+        let mut m = numbered(2, 3);
+        m.transpose();
+        assert_eq!(vec![vec![0, 10], vec![1, 11], vec![2, 12]], rows(&m));
+    }
+    #[test]
+    fn swap() {
+        let mut m = numbered(2, 3);
+        m.swap_rows(0, 1);
+        m.swap_cols(0, 2);
+        assert_eq!(vec![vec![12, 11, 10], vec![2, 1, 0]], rows(&m));
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Clone,
+{
+    /// Add a row at the bottom of the matrix
+    ///
+    /// The first row added to an empty matrix sets its number of columns.
+    ///
+    /// # Panics
+    /// When the row doesn't have one cell per column
+    pub fn push_row(&mut self, row: Vec<T>) {
+        let n_rows = self.n_rows();
+        self.insert_row(n_rows, row);
+    }
+    /// Insert a row before the row at the given index, shifting the following rows down
+    ///
+    /// # Panics
+    /// When the row doesn't have one cell per column, or when `i > n_rows`
+    pub fn insert_row(&mut self, i: usize, row: Vec<T>) {
+        if self.n_rows == 0 && self.n_cols == 0 {
+            self.n_cols = row.len();
+        }
+        if row.len() != self.n_cols {
+            panic!(
+                "a row of {} cells can't be added to a matrix of {} columns",
+                row.len(),
+                self.n_cols
+            );
+        }
+        check_insertion_index("row", i, self.n_rows());
+        if i == self.n_rows {
+            self.hidden = 0;
+        }
+        let at = i * self.n_cols;
+        self.cells.splice(at..at, row);
+        self.n_rows += 1;
+    }
+    /// Remove the row at the given index and return its cells, shifting the following rows up
+    ///
+    /// # Panics
+    /// When the row is out of bounds
+    pub fn remove_row(&mut self, i: usize) -> Vec<T> {
+        check_index("row", i, self.n_rows());
+        if i + 1 == self.n_rows {
+            self.hidden = 0;
+        }
+        let at = i * self.n_cols;
+        self.n_rows -= 1;
+        self.cells.drain(at..at + self.n_cols).collect::<Vec<_>>()
+    }
+    /// Add a column at the right of the matrix
+    ///
+    /// The first column added to an empty matrix sets its number of rows.
+    ///
+    /// # Panics
+    /// When the column doesn't have one cell per row
+    pub fn push_col(&mut self, col: Vec<T>) {
+        let n_cols = self.n_cols();
+        self.insert_col(n_cols, col);
+    }
+    /// Insert a column before the column at the given index, shifting the following columns right
+    ///
+    /// # Panics
+    /// When the column doesn't have one cell per row, or when `j > n_cols`
+    pub fn insert_col(&mut self, j: usize, col: Vec<T>) {
+        if (self.n_rows > 0 || self.n_cols > 0) && col.len() != self.n_rows {
+            panic!(
+                "a column of {} cells can't be added to a matrix of {} rows",
+                col.len(),
+                self.n_rows()
+            );
+        }
+        check_insertion_index("column", j, self.n_cols);
+        let n_cols = self.n_cols;
+        let col_len = col.len();
+        let mut col = col.into_iter();
+        let mut cells = Vec::with_capacity(self.cells.len() + col.len());
+        for (i, cell) in self.cells.drain(..).enumerate() {
+            if i % n_cols == j {
+                cells.extend(col.next());
+            }
+            cells.push(cell);
+            if j == n_cols && i % n_cols == n_cols - 1 {
+                cells.extend(col.next());
+            }
+        }
+        // Only left when the matrix had no column
+        cells.extend(col);
+        self.cells = cells;
+        self.n_rows = col_len;
+        self.n_cols += 1;
+        self.hidden = 0;
+    }
+    /// Remove the column at the given index and return its cells, shifting the following columns left
+    ///
+    /// # Panics
+    /// When the column is out of bounds
+    pub fn remove_col(&mut self, j: usize) -> Vec<T> {
+        check_index("column", j, self.n_cols);
+        let n_cols = self.n_cols;
+        let (removed, kept): (Vec<_>, Vec<_>) = self
+            .cells
+            .drain(..)
+            .enumerate()
+            .partition(|(i, _)| i % n_cols == j);
+        self.cells = kept.into_iter().map(|(_, cell)| cell).collect::<Vec<_>>();
+        self.n_cols -= 1;
+        self.hidden = 0;
+        removed
+            .into_iter()
+            .map(|(_, cell)| cell)
+            .collect::<Vec<_>>()
+    }
+    /// Change the number of rows and columns, keeping the cells at the top left
+    /// and filling new cells with copies of `fill`
+    pub fn resize(&mut self, n_rows: usize, n_cols: usize, fill: T) {
+        let resized = Matrix::from_fn(n_rows, n_cols, |r, c| {
+            self.get(r, c).cloned().unwrap_or_else(|| fill.clone())
+        });
+        *self = resized;
+    }
+    /// Swap rows and columns, so that the cell at (row, col) moves to (col, row)
+    pub fn transpose(&mut self) {
+        let transposed = Matrix::from_fn(self.n_cols(), self.n_rows(), |r, c| self[(c, r)].clone());
+        *self = transposed;
+    }
+    /// Swap two rows
+    ///
+    /// # Panics
+    /// When either row is out of bounds
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        check_index("row", a, self.n_rows());
+        check_index("row", b, self.n_rows());
+        self.hidden = 0;
+        for c in 0..self.n_cols {
+            self.cells.swap(a * self.n_cols + c, b * self.n_cols + c);
+        }
+    }
+    /// Swap two columns
+    ///
+    /// # Panics
+    /// When either column is out of bounds
+    pub fn swap_cols(&mut self, a: usize, b: usize) {
+        check_index("column", a, self.n_cols);
+        check_index("column", b, self.n_cols);
+        self.hidden = 0;
+        for r in 0..self.n_rows() {
+            self.cells.swap(r * self.n_cols + a, r * self.n_cols + b);
+        }
+    }
+}
+
+fn check_insertion_index(what: &str, i: usize, len: usize) {
+    if i > len {
+        panic!(
+            "can't insert a {} at {} in a matrix of {} {}s",
+            what, i, len, what
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! Rotate, flip and roll the cells of a `Matrix`
+//!
+//! Each transformation comes in place (`rotate_cw`) and as a new matrix (`rotated_cw`).
+//! Rotations swap the number of rows and columns unless they are by 180°.
+
+use super::Matrix;
+
+#[cfg(test)]
+mod transform_tests {
+    use crate::matrix::fixtures::{numbered, rows};
+    use crate::matrix::Matrix;
+    #[test]
+    fn rotations() {
+        assert_eq!(
+            vec![vec![10, 0], vec![11, 1], vec![12, 2]],
+            rows(&numbered(2, 3).rotated_cw())
+        );
+        assert_eq!(
+            vec![vec![2, 12], vec![1, 11], vec![0, 10]],
+            rows(&numbered(2, 3).rotated_ccw())
+        );
+        assert_eq!(
+            vec![vec![12, 11, 10], vec![2, 1, 0]],
+            rows(&numbered(2, 3).rotated_180())
+        );
+        let mut m = numbered(2, 3);
+        m.rotate_cw();
+        m.rotate_cw();
+        assert_eq!(rows(&numbered(2, 3).rotated_180()), rows(&m));
+        m.rotate_180();
+        m.rotate_ccw();
+        m.rotate_cw();
+        assert_eq!(rows(&numbered(2, 3)), rows(&m));
+    }
+    #[test]
+    fn flips() {
+        assert_eq!(
+            vec![vec![2, 1, 0], vec![12, 11, 10]],
+            rows(&numbered(2, 3).flipped_horizontal())
+        );
+        assert_eq!(
+            vec![vec![10, 11, 12], vec![0, 1, 2]],
+            rows(&numbered(2, 3).flipped_vertical())
+        );
+        let mut m = numbered(2, 3);
+        m.flip_horizontal();
+        m.flip_vertical();
+        assert_eq!(rows(&numbered(2, 3).rotated_180()), rows(&m));
+    }
+    #[test]
+    fn transposed() {
+        assert_eq!(
+            vec![vec![0, 10], vec![1, 11], vec![2, 12]],
+            rows(&numbered(2, 3).transposed())
+        );
+    }
+    #[test]
+    fn rolls() {
+        assert_eq!(
+            vec![vec![10, 11, 12], vec![0, 1, 2]],
+            rows(&numbered(2, 3).rolled_rows(1))
+        );
+        assert_eq!(
+            vec![vec![2, 0, 1], vec![12, 10, 11]],
+            rows(&numbered(2, 3).rolled_cols(1))
+        );
+        assert_eq!(
+            vec![vec![1, 2, 0], vec![11, 12, 10]],
+            rows(&numbered(2, 3).rolled_cols(-4))
+        );
+        let mut m = numbered(2, 3);
+        m.roll_rows(-3);
+        m.roll_cols(3);
+        assert_eq!(rows(&numbered(2, 3).flipped_vertical()), rows(&m));
+        let mut empty = Matrix::<u8>::from_rows(vec![]).unwrap();
+        empty.roll_rows(1);
+        empty.roll_cols(1);
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Clone,
+{
+    /// A copy of the matrix rotated by 90° clockwise
+    ///
+    /// # Example: reusing a left merge for every direction of 2048
+    /// ```
+    /// let board = matrix_display::matrix::Matrix::from([[2, 0], [4, 8]]);
+    /// // Merging up is merging left on the board rotated counter clockwise
+    /// let up = board.rotated_ccw();
+    /// // ...merge each row of `up` to the left, then rotate it back with `rotated_cw`
+    /// assert_eq!(board, up.rotated_cw());
+    /// ```
+    pub fn rotated_cw(&self) -> Matrix<T> {
+        let n_rows = self.n_rows();
+        Matrix::from_fn(self.n_cols(), n_rows, |r, c| {
+            self[(n_rows - 1 - c, r)].clone()
+        })
+    }
+    /// A copy of the matrix rotated by 90° counter clockwise
+    pub fn rotated_ccw(&self) -> Matrix<T> {
+        let n_cols = self.n_cols();
+        Matrix::from_fn(n_cols, self.n_rows(), |r, c| {
+            self[(c, n_cols - 1 - r)].clone()
+        })
+    }
+    /// A copy of the matrix rotated by 180°
+    pub fn rotated_180(&self) -> Matrix<T> {
+        let mut rotated = self.clone();
+        rotated.rotate_180();
+        rotated
+    }
+    /// Rotate the matrix by 90° clockwise
+    pub fn rotate_cw(&mut self) {
+        *self = self.rotated_cw();
+    }
+    /// Rotate the matrix by 90° counter clockwise
+    pub fn rotate_ccw(&mut self) {
+        *self = self.rotated_ccw();
+    }
+    /// Rotate the matrix by 180°
+    pub fn rotate_180(&mut self) {
+        self.hidden = 0;
+        self.cells.reverse();
+    }
+    /// A copy of the matrix mirrored left to right
+    pub fn flipped_horizontal(&self) -> Matrix<T> {
+        let mut flipped = self.clone();
+        flipped.flip_horizontal();
+        flipped
+    }
+    /// A copy of the matrix mirrored top to bottom
+    ///
+    /// # Example: showing a chess board from the black player's side
+    /// ```
+    /// let board = matrix_display::matrix::Matrix::from([['♜', '♞'], ['♖', '♘']]);
+    /// assert_eq!(&['♖', '♘'], board.flipped_vertical().row(0));
+    /// ```
+    pub fn flipped_vertical(&self) -> Matrix<T> {
+        let mut flipped = self.clone();
+        flipped.flip_vertical();
+        flipped
+    }
+    /// Mirror the matrix left to right
+    pub fn flip_horizontal(&mut self) {
+        self.hidden = 0;
+        for row in self.rows_mut() {
+            row.reverse();
+        }
+    }
+    /// Mirror the matrix top to bottom
+    pub fn flip_vertical(&mut self) {
+        let n_rows = self.n_rows();
+        for r in 0..n_rows / 2 {
+            self.swap_rows(r, n_rows - 1 - r);
+        }
+    }
+    /// A transposed copy of the matrix, see `transpose`
+    pub fn transposed(&self) -> Matrix<T> {
+        let mut transposed = self.clone();
+        transposed.transpose();
+        transposed
+    }
+    /// A copy of the matrix with its rows shifted down by `by` rows, wrapping around
+    ///
+    /// Negative values shift up.
+    pub fn rolled_rows(&self, by: isize) -> Matrix<T> {
+        let mut rolled = self.clone();
+        rolled.roll_rows(by);
+        rolled
+    }
+    /// A copy of the matrix with its columns shifted right by `by` columns, wrapping around
+    ///
+    /// Negative values shift left.
+    pub fn rolled_cols(&self, by: isize) -> Matrix<T> {
+        let mut rolled = self.clone();
+        rolled.roll_cols(by);
+        rolled
+    }
+    /// Shift the rows down by `by` rows, wrapping around; negative values shift up
+    pub fn roll_rows(&mut self, by: isize) {
+        let shift = wrap(by, self.n_rows()) * self.n_cols;
+        self.hidden = 0;
+        self.cells.rotate_right(shift);
+    }
+    /// Shift the columns right by `by` columns, wrapping around; negative values shift left
+    pub fn roll_cols(&mut self, by: isize) {
+        let shift = wrap(by, self.n_cols);
+        self.hidden = 0;
+        for row in self.rows_mut() {
+            row.rotate_right(shift);
+        }
+    }
+}
+
+/// The equivalent shift in 0..len of a shift by `by`, wrapping around
+fn wrap(by: isize, len: usize) -> usize {
+    if len == 0 {
+        0
+    } else {
+        by.rem_euclid(len as isize) as usize
+    }
+}
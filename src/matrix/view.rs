@@ -0,0 +1,308 @@
+//! Borrowed rectangular regions of a `Matrix`
+//!
+//! A `MatrixView` reads a region of a matrix and a `MatrixViewMut` edits it, without copying any cell.
+//! Coordinates within a view are relative to its top left cell.
+//! Both can be displayed with `MatrixDisplay::from_view` and `MatrixDisplay::from_view_mut`.
+
+use super::{check_index, Matrix};
+use std::ops::{Bound, Index, IndexMut, Range, RangeBounds};
+
+#[cfg(test)]
+mod view_tests {
+    use crate::matrix::fixtures::numbered;
+    #[test]
+    fn view() {
+        let m = numbered(3, 4);
+        let v = m.view(1.., 1..3);
+        assert_eq!((2, 2), (v.n_rows(), v.n_cols()));
+        assert_eq!(
+            vec![vec![11, 12], vec![21, 22]],
+            v.rows().map(|r| r.to_vec()).collect::<Vec<_>>()
+        );
+        assert_eq!(&[21, 22], v.row(1));
+        assert_eq!(vec![12, 22], v.col(1).cloned().collect::<Vec<_>>());
+        assert_eq!(vec![11, 12, 21, 22], v.iter().cloned().collect::<Vec<_>>());
+        assert_eq!(Some(((1, 0), &21)), v.indexed_iter().nth(2));
+        assert_eq!(22, v[(1, 1)]);
+        assert_eq!(None, v.get(0, 2));
+        assert_eq!(23, m.view(.., ..)[(2, 3)]);
+    }
+    #[test]
+    #[should_panic(expected = "columns 2..5 are out of bounds for a matrix of 4 columns")]
+    fn view_out_of_bounds() {
+        numbered(3, 4).view(.., 2..5);
+    }
+    #[test]
+    fn view_mut() {
+        let mut m = numbered(3, 4);
+        {
+            let mut v = m.view_mut(0..2, 2..);
+            v.fill(0);
+            v[(1, 1)] = 1;
+            for x in v.iter_mut() {
+                *x += 5;
+            }
+            assert_eq!(
+                vec![5, 5, 5, 6],
+                v.as_view().iter().cloned().collect::<Vec<_>>()
+            );
+        }
+        assert_eq!(
+            vec![vec![0, 1, 5, 5], vec![10, 11, 5, 6], vec![20, 21, 22, 23]],
+            m.rows().map(|r| r.to_vec()).collect::<Vec<_>>()
+        );
+    }
+}
+
+/// A borrowed, read only, rectangular region of a matrix
+#[derive(Clone)]
+pub struct MatrixView<'a, T>
+where
+    T: Clone,
+{
+    matrix: &'a Matrix<T>,
+    rows: Range<usize>,
+    cols: Range<usize>,
+}
+
+/// A borrowed, mutable, rectangular region of a matrix
+pub struct MatrixViewMut<'a, T>
+where
+    T: Clone,
+{
+    matrix: &'a mut Matrix<T>,
+    rows: Range<usize>,
+    cols: Range<usize>,
+}
+
+impl<T> Matrix<T>
+where
+    T: Clone,
+{
+    /// A read only view of the cells within the given ranges of rows and columns
+    ///
+    /// # Panics
+    /// When a range goes beyond the matrix
+    ///
+    /// # Example:
+    /// ```
+    /// let m = matrix_display::matrix::Matrix::from_fn(100, 100, |r, c| r * c);
+    /// let corner = m.view(90.., 90..);
+    /// assert_eq!((10, 10), (corner.n_rows(), corner.n_cols()));
+    /// ```
+    pub fn view<R, C>(&self, rows: R, cols: C) -> MatrixView<'_, T>
+    where
+        R: RangeBounds<usize>,
+        C: RangeBounds<usize>,
+    {
+        MatrixView {
+            rows: region("rows", rows, self.n_rows()),
+            cols: region("columns", cols, self.n_cols()),
+            matrix: self,
+        }
+    }
+    /// A mutable view of the cells within the given ranges of rows and columns
+    ///
+    /// # Panics
+    /// When a range goes beyond the matrix
+    ///
+    /// # Example: highlighting a region
+    /// ```
+    /// use matrix_display::*;
+    /// let mut m = matrix::Matrix::from_fn(8, 8, |r, c| cell::Cell::new(r * c, 7, 0));
+    /// for cell in m.view_mut(2..4, 2..6).iter_mut() {
+    ///     cell.color.bg = 4;
+    /// }
+    /// ```
+    pub fn view_mut<R, C>(&mut self, rows: R, cols: C) -> MatrixViewMut<'_, T>
+    where
+        R: RangeBounds<usize>,
+        C: RangeBounds<usize>,
+    {
+        MatrixViewMut {
+            rows: region("rows", rows, self.n_rows()),
+            cols: region("columns", cols, self.n_cols()),
+            matrix: self,
+        }
+    }
+}
+
+impl<'a, T> MatrixView<'a, T>
+where
+    T: Clone,
+{
+    /// Number of rows in the view
+    pub fn n_rows(&self) -> usize {
+        self.rows.len()
+    }
+    /// Number of columns in the view
+    pub fn n_cols(&self) -> usize {
+        self.cols.len()
+    }
+    /// Number of cells displayed in the row at the given index of the view,
+    /// fewer than `n_cols` in the ragged last row of a matrix (see `Matrix::ragged`)
+    pub(crate) fn row_len(&self, i: usize) -> usize {
+        if self.rows.start + i + 1 == self.matrix.n_rows() {
+            let len = self.matrix.last_row_len().saturating_sub(self.cols.start);
+            std::cmp::min(len, self.n_cols())
+        } else {
+            self.n_cols()
+        }
+    }
+    /// Access element at given row and column of the view, or None if out of the view
+    pub fn get(&self, row: usize, col: usize) -> Option<&'a T> {
+        if row < self.n_rows() && col < self.n_cols() {
+            self.matrix
+                .get(self.rows.start + row, self.cols.start + col)
+        } else {
+            None
+        }
+    }
+    /// The row at the given index of the view, as a slice
+    ///
+    /// # Panics
+    /// When the row is out of the view
+    pub fn row(&self, i: usize) -> &'a [T] {
+        check_index("row", i, self.n_rows());
+        &self.matrix.row(self.rows.start + i)[self.cols.clone()]
+    }
+    /// Iterate over the rows of the view, each given as a slice
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> {
+        let view = self.clone();
+        (0..self.n_rows()).map(move |i| view.row(i))
+    }
+    /// Iterate over the cells of the column at the given index of the view, from top to bottom
+    ///
+    /// # Panics
+    /// When the column is out of the view
+    pub fn col(&self, j: usize) -> impl Iterator<Item = &'a T> {
+        check_index("column", j, self.n_cols());
+        self.rows().map(move |row| &row[j])
+    }
+    /// Iterate over the cells of the view in row major order
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> {
+        self.rows().flat_map(|row| row.iter())
+    }
+    /// Iterate over the cells of the view along with their (row, col) coordinates within the view
+    pub fn indexed_iter(&self) -> impl Iterator<Item = ((usize, usize), &'a T)> {
+        self.rows()
+            .enumerate()
+            .flat_map(|(r, row)| row.iter().enumerate().map(move |(c, x)| ((r, c), x)))
+    }
+}
+
+impl<'a, T> Index<(usize, usize)> for MatrixView<'a, T>
+where
+    T: Clone,
+{
+    type Output = T;
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        check_index("column", col, self.n_cols());
+        &self.row(row)[col]
+    }
+}
+
+impl<'a, T> MatrixViewMut<'a, T>
+where
+    T: Clone,
+{
+    /// Number of rows in the view
+    pub fn n_rows(&self) -> usize {
+        self.rows.len()
+    }
+    /// Number of columns in the view
+    pub fn n_cols(&self) -> usize {
+        self.cols.len()
+    }
+    /// A read only view of the same region
+    pub fn as_view(&self) -> MatrixView<'_, T> {
+        MatrixView {
+            matrix: self.matrix,
+            rows: self.rows.clone(),
+            cols: self.cols.clone(),
+        }
+    }
+    /// Access element at given row and column of the view, or None if out of the view
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        self.as_view().get(row, col)
+    }
+    /// Mutably access element at given row and column of the view, or None if out of the view
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        if row < self.n_rows() && col < self.n_cols() {
+            self.matrix
+                .get_mut(self.rows.start + row, self.cols.start + col)
+        } else {
+            None
+        }
+    }
+    /// The row at the given index of the view, as a mutable slice
+    ///
+    /// # Panics
+    /// When the row is out of the view
+    pub fn row_mut(&mut self, i: usize) -> &mut [T] {
+        check_index("row", i, self.n_rows());
+        let cols = self.cols.clone();
+        &mut self.matrix.row_mut(self.rows.start + i)[cols]
+    }
+    /// Mutably iterate over the rows of the view, each given as a slice
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        let cols = self.cols.clone();
+        self.matrix
+            .rows_mut()
+            .skip(self.rows.start)
+            .take(self.rows.len())
+            .map(move |row| &mut row[cols.clone()])
+    }
+    /// Mutably iterate over the cells of the view in row major order
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.rows_mut().flat_map(|row| row.iter_mut())
+    }
+    /// Set every cell of the view to a copy of `value`
+    pub fn fill(&mut self, value: T) {
+        for x in self.iter_mut() {
+            *x = value.clone();
+        }
+    }
+}
+
+impl<'a, T> Index<(usize, usize)> for MatrixViewMut<'a, T>
+where
+    T: Clone,
+{
+    type Output = T;
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        check_index("row", row, self.n_rows());
+        check_index("column", col, self.n_cols());
+        &self.matrix[(self.rows.start + row, self.cols.start + col)]
+    }
+}
+
+impl<'a, T> IndexMut<(usize, usize)> for MatrixViewMut<'a, T>
+where
+    T: Clone,
+{
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        &mut self.row_mut(row)[col]
+    }
+}
+
+/// Resolve a range of rows or columns against their number
+fn region<R: RangeBounds<usize>>(what: &str, range: R, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&x) => x,
+        Bound::Excluded(&x) => x + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&x) => x + 1,
+        Bound::Excluded(&x) => x,
+        Bound::Unbounded => len,
+    };
+    if start > end || end > len {
+        panic!(
+            "{} {}..{} are out of bounds for a matrix of {} {}",
+            what, start, end, len, what
+        );
+    }
+    start..end
+}
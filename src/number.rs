@@ -0,0 +1,350 @@
+//! Format numbers for display: fixed precision, significant digits, scientific notation,
+//! SI suffixes and percentages, with optional thousands separators and sign alignment
+//!
+//! A `NumberFormat` formats a single value, so it can be used per cell from a styling closure.
+//! `Matrix::format_numbers` formats a whole matrix, per column or per cell, and pads the values of
+//! each column to a common width so that they line up; `Format::fit_widest` then picks the cell
+//! width that fits the widest text of the whole matrix, since all cells share one width.
+
+use crate::matrix::Matrix;
+use crate::Format;
+use unicode_width::UnicodeWidthStr;
+
+#[cfg(test)]
+mod number_tests {
+    use super::{NumberFormat, Sign};
+    use crate::matrix::Matrix;
+    use crate::Format;
+    #[test]
+    fn fixed() {
+        let f = NumberFormat::fixed(2);
+        assert_eq!("1.23", f.format(1.23456));
+        assert_eq!("-2.50", f.format(-2.5));
+        assert_eq!("0.00", f.format(-0.001));
+        assert_eq!("1235", NumberFormat::fixed(0).format(1234.6));
+    }
+    #[test]
+    fn significant() {
+        let f = NumberFormat::significant(3);
+        assert_eq!("1.23", f.format(1.23456));
+        assert_eq!("0.00123", f.format(0.0012345));
+        assert_eq!("123000", f.format(123456.0));
+        assert_eq!("10.0", f.format(9.999));
+        assert_eq!("0.00", f.format(0.0));
+    }
+    #[test]
+    fn scientific() {
+        assert_eq!("1.23e5", NumberFormat::scientific(2).format(123456.0));
+        assert_eq!("-5.0e-3", NumberFormat::scientific(1).format(-0.005));
+    }
+    #[test]
+    fn si() {
+        let f = NumberFormat::si(1);
+        assert_eq!("1.2k", f.format(1234.0));
+        assert_eq!("3.4M", f.format(3_400_000.0));
+        assert_eq!("1.0M", f.format(999_999.0));
+        assert_eq!("12.0", f.format(12.0));
+        assert_eq!("-2.5m", f.format(-0.0025));
+        assert_eq!("0.0", f.format(0.0));
+    }
+    #[test]
+    fn percent() {
+        assert_eq!("12.5%", NumberFormat::percent(1).format(0.125));
+        assert_eq!("100%", NumberFormat::percent(0).format(1.0));
+    }
+    #[test]
+    fn thousands() {
+        let f = NumberFormat::fixed(2).with_thousands(',');
+        assert_eq!("1,234,567.89", f.format(1_234_567.891));
+        assert_eq!("-123.00", f.format(-123.0));
+        assert_eq!(
+            "-1,000",
+            NumberFormat::fixed(0).with_thousands(',').format(-1000.0)
+        );
+    }
+    #[test]
+    fn sign() {
+        let f = NumberFormat::fixed(1).with_sign(Sign::Always);
+        assert_eq!("+1.0", f.format(1.0));
+        assert_eq!("-1.0", f.format(-1.0));
+        assert_eq!(" 0.0", f.format(0.0));
+        assert_eq!(" 0.0", f.format(-0.01));
+        assert_eq!(
+            " 1.0",
+            NumberFormat::fixed(1).with_sign(Sign::Space).format(1.0)
+        );
+    }
+    #[test]
+    fn non_finite() {
+        let f = NumberFormat::fixed(2);
+        assert_eq!("NaN", f.format(f64::NAN));
+        assert_eq!("-inf", f.format(f64::NEG_INFINITY));
+        assert_eq!("+inf", f.with_sign(Sign::Always).format(f64::INFINITY));
+    }
+    #[test]
+    fn columns_line_up() {
+        let m = Matrix::from([[1.5, 2000.0], [-10.25, 3.0]]);
+        let texts = m.format_numbers(|_, c| {
+            if c == 0 {
+                NumberFormat::fixed(2)
+            } else {
+                NumberFormat::si(1)
+            }
+        });
+        assert_eq!(
+            Matrix::from([["  1.50", "2.0k"], ["-10.25", " 3.0"]]).map(|s| s.to_string()),
+            texts
+        );
+        let f = Format::fit_widest(&texts, 1);
+        assert_eq!((8, 1), (f.cell_w, f.cell_h));
+    }
+    #[test]
+    fn any_primitive_number() {
+        let m = Matrix::from([[1_u64, 20], [300, u64::MAX]]);
+        let texts = m.format_numbers(|_, _| NumberFormat::si(0));
+        assert_eq!("18E", texts[(1, 1)]);
+        let m = Matrix::from([[-1_i64], [2]]);
+        assert_eq!(
+            " 2",
+            m.format_numbers(|_, _| NumberFormat::fixed(0))[(1, 0)]
+        );
+    }
+}
+
+/// How a number is written
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Notation {
+    /// A fixed number of decimals: 3.14
+    Fixed(usize),
+    /// A number of significant digits: 0.00123, 123000
+    Significant(usize),
+    /// Scientific notation with a number of decimals: 1.23e5
+    Scientific(usize),
+    /// An SI suffix with a number of decimals: 1.2k, 3.4M, 2.5m
+    Si(usize),
+    /// A percentage of 1 with a number of decimals: 12.5%
+    Percent(usize),
+}
+
+/// When to show the sign of a number
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Sign {
+    /// Only negative numbers get a '-'
+    Negative,
+    /// Positive numbers get a '+', negative numbers a '-', and zero a space so that its digits line up
+    Always,
+    /// Positive numbers get a space where negative numbers get a '-', so that their digits line up
+    Space,
+}
+
+/// A way to format numbers: a notation, an optional thousands separator and a sign policy
+///
+/// # Example:
+/// ```
+/// use matrix_display::number::{NumberFormat, Sign};
+/// let f = NumberFormat::fixed(2).with_thousands(',').with_sign(Sign::Space);
+/// assert_eq!(" 1,234.50", f.format(1234.5));
+/// assert_eq!("-1,234.50", f.format(-1234.5));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NumberFormat {
+    pub notation: Notation,
+    pub thousands: Option<char>,
+    pub sign: Sign,
+}
+impl NumberFormat {
+    /// Construct a format with the given notation, no thousands separator, and signs for negative numbers only
+    pub fn new(notation: Notation) -> NumberFormat {
+        NumberFormat {
+            notation,
+            thousands: None,
+            sign: Sign::Negative,
+        }
+    }
+    /// A fixed number of decimals
+    pub fn fixed(decimals: usize) -> NumberFormat {
+        NumberFormat::new(Notation::Fixed(decimals))
+    }
+    /// A number of significant digits
+    pub fn significant(digits: usize) -> NumberFormat {
+        NumberFormat::new(Notation::Significant(digits))
+    }
+    /// Scientific notation with a number of decimals
+    pub fn scientific(decimals: usize) -> NumberFormat {
+        NumberFormat::new(Notation::Scientific(decimals))
+    }
+    /// An SI suffix with a number of decimals
+    pub fn si(decimals: usize) -> NumberFormat {
+        NumberFormat::new(Notation::Si(decimals))
+    }
+    /// A percentage with a number of decimals
+    pub fn percent(decimals: usize) -> NumberFormat {
+        NumberFormat::new(Notation::Percent(decimals))
+    }
+    /// Separate groups of thousands in the integer part with `separator`
+    ///
+    /// This doesn't apply to scientific notation.
+    pub fn with_thousands(self, separator: char) -> NumberFormat {
+        NumberFormat {
+            thousands: Some(separator),
+            ..self
+        }
+    }
+    /// Show signs according to `sign`
+    pub fn with_sign(self, sign: Sign) -> NumberFormat {
+        NumberFormat { sign, ..self }
+    }
+    /// Format a number
+    pub fn format(&self, x: f64) -> String {
+        if x.is_nan() {
+            return "NaN".to_string();
+        }
+        let magnitude = if x.is_infinite() {
+            "inf".to_string()
+        } else {
+            self.magnitude(x.abs())
+        };
+        let is_zero = x.is_finite() && !magnitude.chars().any(|c| c.is_ascii_digit() && c != '0');
+        let sign = match self.sign {
+            _ if x < 0.0 && !is_zero => "-",
+            Sign::Always if !is_zero => "+",
+            Sign::Always | Sign::Space => " ",
+            _ => "",
+        };
+        format!("{}{}", sign, magnitude)
+    }
+    /// Format a positive number, without its sign
+    fn magnitude(&self, x: f64) -> String {
+        match self.notation {
+            Notation::Fixed(decimals) => self.group(format!("{:.*}", decimals, x)),
+            Notation::Significant(digits) => self.group(significant(x, digits)),
+            Notation::Scientific(decimals) => format!("{:.*e}", decimals, x),
+            Notation::Si(decimals) => {
+                let (value, suffix) = si(x, decimals);
+                format!(
+                    "{}{}",
+                    self.group(format!("{:.*}", decimals, value)),
+                    suffix
+                )
+            }
+            Notation::Percent(decimals) => {
+                format!("{}%", self.group(format!("{:.*}", decimals, x * 100.0)))
+            }
+        }
+    }
+    /// Insert the thousands separator, if any, in the integer part of formatted digits
+    fn group(&self, digits: String) -> String {
+        let separator = match self.thousands {
+            Some(separator) => separator,
+            None => return digits,
+        };
+        let (integer, decimals) = match digits.find('.') {
+            Some(i) => digits.split_at(i),
+            None => (digits.as_str(), ""),
+        };
+        let n = integer.len();
+        let mut grouped = String::new();
+        for (i, c) in integer.chars().enumerate() {
+            if i > 0 && (n - i) % 3 == 0 {
+                grouped.push(separator);
+            }
+            grouped.push(c);
+        }
+        grouped + decimals
+    }
+}
+
+/// Write a positive number with the given number of significant digits
+fn significant(x: f64, digits: usize) -> String {
+    let digits = std::cmp::max(1, digits) as i32;
+    if x == 0.0 {
+        return format!("{:.*}", (digits - 1) as usize, 0.0);
+    }
+    let scale = 10_f64.powi(digits - 1 - x.log10().floor() as i32);
+    let rounded = (x * scale).round() / scale;
+    let exponent = rounded.log10().floor() as i32;
+    let decimals = std::cmp::max(0, digits - 1 - exponent) as usize;
+    format!("{:.*}", decimals, rounded)
+}
+
+/// Split a positive number into a value below 1000 and its SI suffix
+fn si(x: f64, decimals: usize) -> (f64, &'static str) {
+    const SUFFIXES: [&str; 11] = ["p", "n", "µ", "m", "", "k", "M", "G", "T", "P", "E"];
+    const UNIT: i32 = 4;
+    if x == 0.0 {
+        return (0.0, "");
+    }
+    let clamp = |e: i32| std::cmp::min(std::cmp::max(e, -UNIT), SUFFIXES.len() as i32 - 1 - UNIT);
+    let mut e = clamp((x.log10() / 3.0).floor() as i32);
+    let round = |v: f64| {
+        let scale = 10_f64.powi(decimals as i32);
+        (v * scale).round() / scale
+    };
+    // Rounding may carry the value over to the next suffix: 999.99k is 1.0M
+    if round(x / 1000_f64.powi(e)) >= 1000.0 {
+        e = clamp(e + 1);
+    }
+    (x / 1000_f64.powi(e), SUFFIXES[(e + UNIT) as usize])
+}
+
+/// Numbers that can be read as a f64, for formatting or coloring
+///
+/// Unlike `Into<f64>`, it covers every primitive number, i64, u64 and usize included:
+/// those beyond 2^53 lose their lowest digits.
+pub trait ToF64 {
+    fn to_f64(&self) -> f64;
+}
+
+macro_rules! to_f64 {
+    ($($t:ty),*) => {
+        $(
+            impl ToF64 for $t {
+                fn to_f64(&self) -> f64 {
+                    *self as f64
+                }
+            }
+        )*
+    };
+}
+to_f64!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+impl<T> Matrix<T>
+where
+    T: Clone + ToF64,
+{
+    /// Format each number with the `NumberFormat` given by `format(row, col)`,
+    /// right aligning the values of each column to a common width
+    ///
+    /// # Example: a different format for each column
+    /// ```
+    /// use matrix_display::matrix::Matrix;
+    /// use matrix_display::number::NumberFormat;
+    /// let formats = [NumberFormat::fixed(1), NumberFormat::percent(0)];
+    /// let texts = Matrix::from([[12.5, 0.5], [3.0, 0.25]]).format_numbers(|_, c| formats[c]);
+    /// assert_eq!(Matrix::from([["12.5".to_string(), "50%".to_string()], [" 3.0".to_string(), "25%".to_string()]]), texts);
+    /// ```
+    pub fn format_numbers<F>(&self, format: F) -> Matrix<String>
+    where
+        F: Fn(usize, usize) -> NumberFormat,
+    {
+        let texts = self.map_indexed(|r, c, x| format(r, c).format(x.to_f64()));
+        let widths = texts
+            .cols()
+            .map(|col| col.map(|text| text.width()).max().unwrap_or(0))
+            .collect::<Vec<_>>();
+        texts.map_indexed(|_, c, text| {
+            let padding = widths[c] - text.width();
+            " ".repeat(padding) + text
+        })
+    }
+}
+
+impl Format {
+    /// The narrowest format fitting the widest text of the matrix, with a blank character on each side
+    ///
+    /// All the cells of a display share this width, so the texts of narrower columns get padded to it.
+    pub fn fit_widest(texts: &Matrix<String>, cell_h: usize) -> Format {
+        let widest = texts.iter().map(|text| text.width()).max().unwrap_or(0);
+        Format::new(widest + 2, cell_h)
+    }
+}
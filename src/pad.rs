@@ -47,10 +47,10 @@ mod pad_tests {
     }
 }
 
+#[allow(clippy::module_inception)]
 mod pad {
 
     use crate::pad::unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
-    use std;
 
     pub struct Pad {
         pub before: usize,
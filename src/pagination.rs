@@ -0,0 +1,254 @@
+//! Split a matrix too large for a page into several printable pages
+//!
+//! The matrix is first split into bands of columns that fit the page width,
+//! then each band into bands of rows that fit the page height.
+//! Frozen rows and columns are repeated on every page, so they act as headers,
+//! and each page starts with a label such as "rows 1–20 of 100, cols 1–12 of 40",
+//! or "rows 1–2 + 41–58 of 100, cols 1–12 of 40" when the frozen rows are followed by rows further down.
+//! The label is cropped to the page width.
+
+use crate::cell::RenderCell;
+use crate::style::BordersStyle;
+use crate::{
+    border_width, crop_text, join_lines, plain_style, separator_width, Band, GenericMatrixDisplay,
+    Line,
+};
+
+use std::io::Write;
+use std::ops::Range;
+
+#[cfg(test)]
+mod pagination_tests {
+    use crate::cell::Cell;
+    use crate::matrix::Matrix;
+    use crate::screen::fixtures::letters;
+    use crate::screen::VirtualScreen;
+    use crate::style::BordersStyle;
+    use crate::{line_width, Format, MatrixDisplay};
+    fn lines(page: &super::Page) -> Vec<String> {
+        let mut s = VirtualScreen::new();
+        page.print(&mut s);
+        s.lines()
+    }
+    #[test]
+    fn column_bands_then_row_bands() {
+        let f = Format::new(1, 1);
+        let mut m = letters();
+        let d = MatrixDisplay::new(&f, &mut m);
+        let pages = d.pages(&BordersStyle::None, (2, 3));
+        assert_eq!(4, pages.len());
+        assert_eq!((0..2, 0..2), (pages[0].rows.clone(), pages[0].cols.clone()));
+        assert_eq!((2..3, 0..2), (pages[1].rows.clone(), pages[1].cols.clone()));
+        assert_eq!((0..2, 2..4), (pages[2].rows.clone(), pages[2].cols.clone()));
+        assert_eq!((2..3, 2..4), (pages[3].rows.clone(), pages[3].cols.clone()));
+        assert_eq!("rows 1–2 of 3, cols 1–2 of 4", pages[0].label);
+        assert_eq!("rows 3–3 of 3, cols 3–4 of 4", pages[3].label);
+        assert_eq!(vec!["ro", "ab", "ef"], lines(&pages[0]));
+        assert_eq!(vec!["ro", "kl"], lines(&pages[3]));
+    }
+    #[test]
+    fn frozen_cells_are_repeated_on_each_page() {
+        let f = Format::new(1, 1);
+        let mut m = letters();
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.set_frozen(1, 1);
+        let pages = d.pages(&BordersStyle::Light, (7, 7));
+        assert_eq!(4, pages.len());
+        assert_eq!(
+            vec![
+                "rows 1–",
+                "┌─┳─┬─┐",
+                "│a┃b│c│",
+                "┣━╋━╋━┫",
+                "│e┃f│g│",
+                "└─┻─┴─┘"
+            ],
+            lines(&pages[0])
+        );
+        assert_eq!(
+            vec!["rows 1–", "┌─┳─┐", "│a┃d│", "┣━╋━┫", "│i┃l│", "└─┻─┘"],
+            lines(&pages[3])
+        );
+        assert_eq!("rows 1–1 + 3–3 of 3, cols 1–1 + 4–4 of 4", pages[3].label);
+    }
+    #[test]
+    fn pages_fit_with_frozen_cells() {
+        let f = Format::new(1, 1);
+        let mut m = letters();
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.set_frozen(1, 1);
+        let sizes = [
+            (BordersStyle::None, (3, 4)),
+            (BordersStyle::None, (4, 4)),
+            (BordersStyle::None, (4, 5)),
+            (BordersStyle::Light, (5, 6)),
+            (BordersStyle::Light, (7, 7)),
+        ];
+        for (borders, (width, height)) in &sizes {
+            for page in d.pages(borders, (*width, *height)) {
+                assert!(page.lines.len() < *height);
+                assert!(page.lines.iter().all(|line| line_width(line) <= *width));
+                assert!(lines(&page)[0].chars().count() <= *width);
+            }
+        }
+    }
+    #[test]
+    fn label_is_cropped_to_the_page_width() {
+        let f = Format::new(1, 1);
+        let mut m = letters();
+        let d = MatrixDisplay::new(&f, &mut m);
+        let pages = d.pages(&BordersStyle::None, (10, 4));
+        assert_eq!("rows 1–3 of 3, cols 1–4 of 4", pages[0].label);
+        assert_eq!(vec!["rows 1–3 o", "abcd", "efgh", "ijkl"], lines(&pages[0]));
+    }
+    #[test]
+    fn empty_matrix_has_no_rows_and_no_cols() {
+        let f = Format::new(1, 1);
+        let mut m = Matrix::<Cell<char>>::new(0, Vec::new());
+        let d = MatrixDisplay::new(&f, &mut m);
+        let pages = d.pages(&BordersStyle::Light, (80, 24));
+        assert_eq!(1, pages.len());
+        assert_eq!("no rows, no cols", pages[0].label);
+        let mut m = Matrix::<Cell<char>>::filled(0, 2, Cell::new(' ', 7, 0));
+        let d = MatrixDisplay::new(&f, &mut m);
+        assert_eq!(
+            "no rows, cols 1–2 of 2",
+            d.pages(&BordersStyle::Light, (80, 24))[0].label
+        );
+    }
+    #[test]
+    fn small_page_still_shows_one_cell() {
+        let f = Format::new(3, 3);
+        let mut m = letters();
+        let d = MatrixDisplay::new(&f, &mut m);
+        assert_eq!(12, d.pages(&BordersStyle::Light, (1, 1)).len());
+    }
+}
+
+/// One page of a matrix: a label followed by the rendered cells
+pub struct Page {
+    /// Describes which cells are on the page, frozen ones included, e.g. "rows 1–20 of 100, cols 1–12 of 40"
+    ///
+    /// It is printed cropped to the page width.
+    pub label: String,
+    /// The range of scrolling rows on the page, frozen rows excluded
+    pub rows: Range<usize>,
+    /// The range of scrolling columns on the page, frozen columns excluded
+    pub cols: Range<usize>,
+    lines: Vec<Line>,
+    width: usize,
+}
+impl Page {
+    /// Render the page, label included, into a Vec<ANSIString>
+    pub fn render(&self) -> Vec<ansi_term::ANSIString<'static>> {
+        let mut lines = vec![vec![plain_style().paint(crop_text(&self.label, self.width))]];
+        lines.extend(self.lines.iter().cloned());
+        join_lines(lines)
+    }
+    /// Print the page, label included
+    pub fn print<Out: Write>(&self, out: &mut Out) {
+        write!(out, "{}", ansi_term::ANSIStrings(&self.render())).unwrap();
+    }
+}
+
+impl<'a, C> GenericMatrixDisplay<'a, C>
+where
+    C: RenderCell + Clone + 'a,
+{
+    /// Split the matrix into pages of at most (width, height) characters, label line included
+    ///
+    /// Only whole cells are printed, but a page always holds at least one scrolling cell,
+    /// even when that doesn't fit. The viewport, if any, is ignored: pages cover the whole matrix.
+    pub fn pages(&self, borders: &BordersStyle, (width, height): (usize, usize)) -> Vec<Page> {
+        let b = border_width(borders);
+        let (frozen_rows, frozen_cols) = self.frozen;
+        let row_bands = bands(
+            self.n_rows(),
+            frozen_rows,
+            whole_cells_in_chars(
+                height.saturating_sub(1 + separator_width(borders, frozen_rows)),
+                self.fmt.cell_h,
+                b,
+            ),
+        );
+        let col_bands = bands(
+            self.n_cols(),
+            frozen_cols,
+            whole_cells_in_chars(
+                width.saturating_sub(separator_width(borders, frozen_cols)),
+                self.fmt.cell_w,
+                b,
+            ),
+        );
+        col_bands
+            .iter()
+            .flat_map(|cols| {
+                row_bands.iter().map(move |rows| Page {
+                    label: format!(
+                        "{}, {}",
+                        describe("rows", rows, self.n_rows()),
+                        describe("cols", cols, self.n_cols())
+                    ),
+                    rows: rows.scrolling.clone(),
+                    cols: cols.scrolling.clone(),
+                    lines: self.render_region(borders, rows, cols, None),
+                    width,
+                })
+            })
+            .collect::<Vec<_>>()
+    }
+    /// Print every page of the matrix, separated by blank lines
+    pub fn print_pages<Out: Write>(
+        &self,
+        out: &mut Out,
+        borders: &BordersStyle,
+        size: (usize, usize),
+    ) {
+        for (i, page) in self.pages(borders, size).iter().enumerate() {
+            if i > 0 {
+                write!(out, "\r\n").unwrap();
+            }
+            page.print(out);
+        }
+    }
+}
+
+/// Number of whole cells fitting within a length in characters
+fn whole_cells_in_chars(chars: usize, cell_dim: usize, border: usize) -> usize {
+    chars.saturating_sub(border) / (cell_dim + border)
+}
+
+/// Split n cells into bands of at most `capacity` cells, each starting with the frozen cells
+fn bands(n: usize, frozen: usize, capacity: usize) -> Vec<Band> {
+    let frozen = std::cmp::min(frozen, n);
+    let per_band = std::cmp::max(1, capacity.saturating_sub(frozen));
+    if frozen == n {
+        return vec![Band::new(n, frozen, 0, None)];
+    }
+    (frozen..n)
+        .step_by(per_band)
+        .map(|first| Band::new(n, frozen, first, Some(frozen + per_band)))
+        .collect::<Vec<_>>()
+}
+
+/// Describe the cells of a band out of n, e.g. "rows 1–12 of 40", or "no rows" when there are none
+fn describe(name: &str, band: &Band, n: usize) -> String {
+    if n == 0 {
+        format!("no {}", name)
+    } else {
+        format!("{} {} of {}", name, span(band), n)
+    }
+}
+
+/// A 1 based, inclusive description of the cells of a band, e.g. "1–12",
+/// or "1–2 + 25–36" when frozen cells are followed by cells further away
+fn span(band: &Band) -> String {
+    let range = &band.scrolling;
+    if band.frozen == 0 {
+        format!("{}–{}", range.start + 1, range.end)
+    } else if range.start == band.frozen {
+        format!("1–{}", range.end)
+    } else {
+        format!("1–{} + {}–{}", band.frozen, range.start + 1, range.end)
+    }
+}
@@ -0,0 +1,182 @@
+//! The 256 colour terminal palette as RGB values
+//!
+//! - Colours 0 to 15 are the system colours, which terminals are free to redefine
+//! - Colours 16 to 231 are a 6x6x6 colour cube
+//! - Colours 232 to 255 are a grayscale ramp
+//!
+//! `nearest` maps an arbitrary RGB colour to the closest ansi code, only considering the cube and the ramp
+//! since they look the same in every terminal.
+//! `contrast` and `readable` pick foregrounds that stand out from their background, as defined by WCAG.
+//! Cells are drawn with ansi codes, so they compare the usual xterm values of the system colours:
+//! a terminal theme redefining them may render a different contrast.
+//! `contrast_rgb` and `readable_rgb` do the same for true colours.
+
+#[cfg(test)]
+mod palette_tests {
+    use super::{
+        contrast, contrast_rgb, luminance, nearest, readable, readable_rgb, rgb, BLACK, WHITE,
+    };
+    #[test]
+    fn rgb_values() {
+        assert_eq!((0, 0, 0), rgb(0));
+        assert_eq!((255, 255, 255), rgb(15));
+        assert_eq!((0, 0, 0), rgb(16));
+        assert_eq!((255, 0, 0), rgb(196));
+        assert_eq!((95, 135, 175), rgb(67));
+        assert_eq!((8, 8, 8), rgb(232));
+        assert_eq!((238, 238, 238), rgb(255));
+    }
+    #[test]
+    fn nearest_colors() {
+        assert_eq!(196, nearest((250, 10, 5)));
+        assert_eq!(16, nearest((0, 0, 0)));
+        assert_eq!(231, nearest((255, 255, 255)));
+        assert_eq!(244, nearest((128, 128, 128)));
+        for code in 16..=255 {
+            assert_eq!(rgb(code), rgb(nearest(rgb(code))));
+        }
+    }
+    #[test]
+    fn luminance_and_contrast() {
+        assert_eq!(0.0, luminance((0, 0, 0)));
+        assert_eq!(1.0, luminance((255, 255, 255)));
+        assert!(luminance((0, 255, 0)) > luminance((255, 0, 0)));
+        assert_eq!(21.0, contrast(BLACK, WHITE));
+        assert_eq!(contrast(196, 21), contrast(21, 196));
+        assert_eq!(1.0, contrast(244, 244));
+    }
+    #[test]
+    fn readable_foregrounds() {
+        // Good enough foregrounds are kept
+        assert_eq!(7, readable(7, 0, 4.5));
+        assert_eq!(0, readable(0, 231, 4.5));
+        // Others become black or white, whichever stands out more
+        assert_eq!(WHITE, readable(0, 17, 4.5));
+        assert_eq!(BLACK, readable(7, 226, 4.5));
+        assert_eq!(BLACK, readable(244, 244, 4.5));
+    }
+    #[test]
+    fn true_colors() {
+        assert_eq!(21.0, contrast_rgb((0, 0, 0), (255, 255, 255)));
+        assert_eq!(contrast(196, 21), contrast_rgb(rgb(196), rgb(21)));
+        assert_eq!(
+            (10, 20, 30),
+            readable_rgb((10, 20, 30), (250, 250, 250), 4.5)
+        );
+        assert_eq!(
+            (255, 255, 255),
+            readable_rgb((10, 20, 30), (20, 20, 40), 4.5)
+        );
+        assert_eq!((0, 0, 0), readable_rgb((200, 200, 0), (255, 255, 200), 4.5));
+    }
+}
+
+/// Black, from the colour cube
+pub const BLACK: u8 = 16;
+/// White, from the colour cube
+pub const WHITE: u8 = 231;
+
+/// The levels of each channel in the colour cube
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The usual xterm values of the system colours
+const SYSTEM_COLORS: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (128, 0, 0),
+    (0, 128, 0),
+    (128, 128, 0),
+    (0, 0, 128),
+    (128, 0, 128),
+    (0, 128, 128),
+    (192, 192, 192),
+    (128, 128, 128),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (0, 0, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// The RGB value of an ansi colour code
+pub fn rgb(code: u8) -> (u8, u8, u8) {
+    match code {
+        0..=15 => SYSTEM_COLORS[code as usize],
+        16..=231 => {
+            let i = (code - 16) as usize;
+            (
+                CUBE_LEVELS[i / 36],
+                CUBE_LEVELS[(i / 6) % 6],
+                CUBE_LEVELS[i % 6],
+            )
+        }
+        _ => {
+            let level = 8 + 10 * (code - 232);
+            (level, level, level)
+        }
+    }
+}
+
+/// The ansi colour code from the cube or the grayscale ramp closest to an RGB colour
+pub fn nearest(color: (u8, u8, u8)) -> u8 {
+    let distance = |code: u8| {
+        let (r, g, b) = rgb(code);
+        let d = |x: u8, y: u8| (i32::from(x) - i32::from(y)).pow(2);
+        d(r, color.0) + d(g, color.1) + d(b, color.2)
+    };
+    (16..=255)
+        .min_by_key(|&code| distance(code))
+        .expect("the palette isn't empty")
+}
+
+/// The relative luminance of an RGB colour, from 0 for black to 1 for white
+pub fn luminance((r, g, b): (u8, u8, u8)) -> f64 {
+    let linear = |c: u8| {
+        let c = f64::from(c) / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+/// The contrast ratio between two ansi colours, from 1 for identical luminances to 21 for black on white
+pub fn contrast(a: u8, b: u8) -> f64 {
+    contrast_rgb(rgb(a), rgb(b))
+}
+
+/// The contrast ratio between two RGB colours, from 1 for identical luminances to 21 for black on white
+pub fn contrast_rgb(a: (u8, u8, u8), b: (u8, u8, u8)) -> f64 {
+    let (a, b) = (luminance(a), luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// `fg` if it contrasts with `bg` by at least `threshold`, or else black or white, whichever contrasts more
+///
+/// WCAG recommends a threshold of 4.5 for text, and 3 for large text.
+pub fn readable(fg: u8, bg: u8, threshold: f64) -> u8 {
+    if contrast(fg, bg) >= threshold {
+        fg
+    } else if contrast(BLACK, bg) >= contrast(WHITE, bg) {
+        BLACK
+    } else {
+        WHITE
+    }
+}
+
+/// `fg` if it contrasts with `bg` by at least `threshold`, or else black or white, whichever contrasts more
+///
+/// The true colour counterpart of `readable`.
+pub fn readable_rgb(fg: (u8, u8, u8), bg: (u8, u8, u8), threshold: f64) -> (u8, u8, u8) {
+    let (black, white) = (rgb(BLACK), rgb(WHITE));
+    if contrast_rgb(fg, bg) >= threshold {
+        fg
+    } else if contrast_rgb(black, bg) >= contrast_rgb(white, bg) {
+        black
+    } else {
+        white
+    }
+}
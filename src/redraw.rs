@@ -0,0 +1,318 @@
+//! Redraw a matrix in place, only emitting what changed since the previous frame
+//!
+//! `Redraw` remembers the last frame it rendered from a `MatrixDisplay`.
+//! The first frame is drawn in full at a fixed origin on the screen.
+//! Subsequent frames are compared with the previous one as they would appear on screen:
+//! the cursor is moved to each run of characters whose value or color changed, and only those get repainted.
+//! This way, a full-screen board can be updated without flickering, whatever its viewport or borders.
+//!
+//! Coordinates are terminal cursor positions in characters, starting at (1, 1) in the top left corner.
+
+use crate::cell::RenderCell;
+use crate::screen::VirtualScreen;
+use crate::style::BordersStyle;
+use crate::{join_lines, GenericMatrixDisplay, Line};
+
+use std::io::Write;
+
+#[cfg(test)]
+mod redraw_tests {
+    use super::Redraw;
+    use crate::cell::{Cell, RenderCell};
+    use crate::matrix::Matrix;
+    use crate::screen::VirtualScreen;
+    use crate::style::BordersStyle;
+    use crate::viewport::Viewport;
+    use crate::{Format, GenericMatrixDisplay, MatrixDisplay};
+
+    fn board() -> Matrix<Cell<char>> {
+        Matrix::new(
+            2,
+            "abcd"
+                .chars()
+                .map(|c| Cell::new(c, 7, 0))
+                .collect::<Vec<_>>(),
+        )
+    }
+    /// A redraw along with the screen its output is replayed into
+    struct Terminal {
+        redraw: Redraw,
+        screen: VirtualScreen,
+    }
+    impl Terminal {
+        fn at(origin: (usize, usize)) -> Terminal {
+            Terminal {
+                redraw: Redraw::at(origin),
+                screen: VirtualScreen::new(),
+            }
+        }
+        /// Redraw the display and return what was emitted,
+        /// checking that the screen now looks exactly like a full render of the display
+        fn show<C>(&mut self, d: &GenericMatrixDisplay<C>, borders: &BordersStyle) -> String
+        where
+            C: RenderCell + Clone,
+        {
+            let out = ansi_term::ANSIStrings(&self.redraw.render(d, borders)).to_string();
+            self.screen.feed(&out);
+            let mut expected = VirtualScreen::new();
+            d.print(&mut expected, borders);
+            let (ox, oy) = self.redraw.origin;
+            let width = std::cmp::max(self.screen.width(), expected.width() + ox);
+            let height = std::cmp::max(self.screen.height(), expected.height() + oy);
+            for y in 1..=height {
+                for x in 1..=width {
+                    let wanted = if x < ox || y < oy {
+                        Default::default()
+                    } else {
+                        expected.cell((x + 1 - ox, y + 1 - oy))
+                    };
+                    assert_eq!(wanted, self.screen.cell((x, y)), "at ({}, {})", x, y);
+                }
+            }
+            out
+        }
+        fn show_board(&mut self, m: &mut Matrix<Cell<char>>, borders: &BordersStyle) -> String {
+            let f = Format::new(3, 1);
+            let d = MatrixDisplay::new(&f, m);
+            self.show(&d, borders)
+        }
+    }
+    #[test]
+    fn first_frame_is_drawn_in_full() {
+        let mut m = board();
+        let mut t = Terminal::at((1, 1));
+        let out = t.show_board(&mut m, &BordersStyle::Light);
+        assert_eq!(
+            vec![
+                "┌───┬───┐",
+                "│ a │ b │",
+                "├───┼───┤",
+                "│ c │ d │",
+                "└───┴───┘"
+            ],
+            t.screen.lines()
+        );
+        assert_eq!((1, 6), t.screen.cursor());
+        assert!(out.starts_with("\x1b[1;1H"));
+    }
+    #[test]
+    fn unchanged_frame_emits_nothing() {
+        let mut m = board();
+        let mut t = Terminal::at((1, 1));
+        t.show_board(&mut m, &BordersStyle::Light);
+        assert_eq!("", t.show_board(&mut m, &BordersStyle::Light));
+    }
+    #[test]
+    fn only_changed_cells_are_emitted() {
+        let mut m = board();
+        let mut t = Terminal::at((3, 2));
+        t.show_board(&mut m, &BordersStyle::Light);
+        m.at((1, 1)).value = 'z';
+        let out = t.show_board(&mut m, &BordersStyle::Light);
+        assert!(out.starts_with("\x1b[5;9H"));
+        for c in "abc┌┐└┘│".chars() {
+            assert!(!out.contains(c));
+        }
+        assert_eq!((3, 7), t.screen.cursor());
+    }
+    #[test]
+    fn color_change_is_emitted() {
+        let mut m = board();
+        let mut t = Terminal::at((1, 1));
+        t.show_board(&mut m, &BordersStyle::None);
+        m.at((0, 1)).color.bg = 10;
+        let out = t.show_board(&mut m, &BordersStyle::None);
+        assert!(out.starts_with("\x1b[2;1H"));
+        assert!(!out.contains('d'));
+    }
+    #[test]
+    fn border_change_repaints_borders() {
+        let mut m = board();
+        let mut t = Terminal::at((1, 1));
+        t.show_board(&mut m, &BordersStyle::Light);
+        let out = t.show_board(&mut m, &BordersStyle::Double);
+        for c in "abcd".chars() {
+            assert!(!out.contains(c));
+        }
+    }
+    #[test]
+    fn shrinking_frame_clears_what_was_left() {
+        let mut m = board();
+        let mut t = Terminal::at((2, 2));
+        t.show_board(&mut m, &BordersStyle::Light);
+        t.show_board(&mut m, &BordersStyle::None);
+        assert_eq!(vec!["", "  a  b", "  c  d", "", "", ""], t.screen.lines());
+    }
+    #[test]
+    fn scrolling_repaints_viewport() {
+        let f = Format::new(3, 1);
+        let mut m = Matrix::new(
+            4,
+            "abcdefgh"
+                .chars()
+                .map(|c| Cell::new(c, 7, 0))
+                .collect::<Vec<_>>(),
+        );
+        let mut t = Terminal::at((1, 1));
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.set_viewport(Viewport::cells(1, 2).with_scroll_indicators());
+        t.show(&d, &BordersStyle::None);
+        d.scroll(1, 1);
+        let out = t.show(&d, &BordersStyle::None);
+        assert!(!out.contains('h'));
+        assert_eq!(vec![" f  g", "◀ ▲  ▶"], t.screen.lines());
+    }
+    #[test]
+    fn invalidate_redraws_everything() {
+        let mut m = board();
+        let mut t = Terminal::at((1, 1));
+        t.show_board(&mut m, &BordersStyle::Light);
+        t.redraw.invalidate();
+        let out = t.show_board(&mut m, &BordersStyle::Light);
+        for c in "abcd".chars() {
+            assert!(out.contains(c));
+        }
+    }
+}
+
+/// Renders successive frames of a `MatrixDisplay`, repainting only the cells that changed
+///
+/// #Example: updating a board in place
+/// ```
+/// use matrix_display::*;
+/// let format = Format::new(5, 1);
+/// let mut data = matrix::Matrix::new(4, (0..16).map(|x| cell::Cell::new(x, 7, 0)).collect::<Vec<_>>());
+/// let mut redraw = redraw::Redraw::new();
+/// redraw.print(&MatrixDisplay::new(&format, &mut data), &mut std::io::stdout(), &style::BordersStyle::Light);
+/// data.at((2, 1)).value = 42;
+/// // Only the cell that now holds 42 is repainted
+/// redraw.print(&MatrixDisplay::new(&format, &mut data), &mut std::io::stdout(), &style::BordersStyle::Light);
+/// ```
+pub struct Redraw {
+    origin: (usize, usize),
+    previous: Option<VirtualScreen>,
+}
+impl Redraw {
+    /// Construct a redraw that draws the matrix from the top left corner of the screen
+    pub fn new() -> Redraw {
+        Redraw::at((1, 1))
+    }
+    /// Construct a redraw that draws the matrix from the given cursor position (x, y)
+    pub fn at(origin: (usize, usize)) -> Redraw {
+        Redraw {
+            origin,
+            previous: None,
+        }
+    }
+    /// Forget the previous frame, so that the next one gets drawn in full
+    ///
+    /// Call this whenever the screen was cleared or overwritten by something else.
+    pub fn invalidate(&mut self) {
+        self.previous = None;
+    }
+    /// Render the difference between the previous frame and the current state of the display
+    ///
+    /// The first frame is rendered in full.
+    /// When anything was emitted, the cursor is left at the beginning of the line below the matrix.
+    pub fn render<C>(
+        &mut self,
+        display: &GenericMatrixDisplay<C>,
+        borders: &BordersStyle,
+    ) -> Vec<ansi_term::ANSIString<'static>>
+    where
+        C: RenderCell + Clone,
+    {
+        let lines = display.render_lines(borders);
+        let mut frame = VirtualScreen::new();
+        write!(
+            frame,
+            "{}",
+            ansi_term::ANSIStrings(&join_lines(lines.clone()))
+        )
+        .unwrap();
+        let strings = match self.previous {
+            Some(ref previous) => self.changed_cells(previous, &frame),
+            None => self.full_frame(lines),
+        };
+        self.previous = Some(frame);
+        strings
+    }
+    /// Print the difference between the previous frame and the current state of the display
+    pub fn print<C, Out: Write>(
+        &mut self,
+        display: &GenericMatrixDisplay<C>,
+        out: &mut Out,
+        borders: &BordersStyle,
+    ) where
+        C: RenderCell + Clone,
+    {
+        write!(
+            out,
+            "{}",
+            ansi_term::ANSIStrings(&self.render(display, borders))
+        )
+        .unwrap();
+        out.flush().unwrap();
+    }
+    fn full_frame(&self, lines: Vec<Line>) -> Vec<ansi_term::ANSIString<'static>> {
+        let (x, y) = self.origin;
+        let n_lines = lines.len();
+        let mut strings = lines
+            .into_iter()
+            .enumerate()
+            .flat_map(|(i, mut line)| {
+                line.insert(0, move_cursor(x, y + i));
+                line
+            })
+            .collect::<Vec<_>>();
+        strings.push(move_cursor(x, y + n_lines));
+        strings
+    }
+    fn changed_cells(
+        &self,
+        previous: &VirtualScreen,
+        current: &VirtualScreen,
+    ) -> Vec<ansi_term::ANSIString<'static>> {
+        let (x, y) = self.origin;
+        let width = std::cmp::max(previous.width(), current.width());
+        let height = std::cmp::max(previous.height(), current.height());
+        let mut strings = Vec::new();
+        for line in 1..=height {
+            let mut in_run = false;
+            for col in 1..=width {
+                let after = current.cell((col, line));
+                if previous.cell((col, line)) == after {
+                    in_run = false;
+                    continue;
+                }
+                if !in_run {
+                    // A run can't start in the middle of a double width character
+                    let start = if after.ch == '\0' { col - 1 } else { col };
+                    strings.push(move_cursor(x + start - 1, y + line - 1));
+                    if start < col {
+                        let wide = current.cell((start, line));
+                        strings.push(wide.style.paint(wide.ch.to_string()));
+                    }
+                    in_run = true;
+                }
+                if after.ch != '\0' {
+                    strings.push(after.style.paint(after.ch.to_string()));
+                }
+            }
+        }
+        if !strings.is_empty() {
+            strings.push(move_cursor(x, y + current.height()));
+        }
+        strings
+    }
+}
+impl Default for Redraw {
+    fn default() -> Redraw {
+        Redraw::new()
+    }
+}
+
+/// Escape sequence that moves the cursor to the position (x, y)
+fn move_cursor(x: usize, y: usize) -> ansi_term::ANSIString<'static> {
+    ansi_term::ANSIString::from(format!("\x1b[{};{}H", y, x))
+}
@@ -0,0 +1,358 @@
+//! Conditional formatting: restyle cells that meet conditions, at render time
+//!
+//! `Rules` are a list of conditions, each with the `Highlight` applied to the cells meeting it.
+//! Conditions are about the value of a cell, its rank among all values, its position, or how it compares
+//! to its neighbours.
+//!
+//! Precedence: a cell starts with its own colors and attributes, tinted by the display's banding if any,
+//! then every rule it meets applies in the order the rules were added. A later rule's colors replace an earlier one's, while attributes add up.
+//! A readable foreground, if the display asks for one, is picked last.
+
+use crate::cell::{AnsiColor, Attributes, RenderCell};
+
+#[cfg(test)]
+mod rules_tests {
+    use super::{Condition, Highlight, Rules};
+    use crate::cell::{AnsiColor, Attributes, Cell};
+    use crate::matrix::Matrix;
+    use crate::screen::VirtualScreen;
+    use crate::style::BordersStyle;
+    use crate::{Format, MatrixDisplay};
+    use ansi_term::Colour::Fixed;
+    fn values() -> Matrix<Cell<u32>> {
+        // 1 2 2
+        // 4 5 6
+        // 7 8 9
+        // 0 3 3
+        Matrix::from([[1, 2, 2], [4, 5, 6], [7, 8, 9], [0, 3, 3]]).map(|&x| Cell::new(x, 7, 0))
+    }
+    fn matching(rules: &Rules<Cell<u32>>, m: &Matrix<Cell<u32>>) -> Matrix<bool> {
+        let cutoffs = rules.cutoffs(m.n_rows(), m.n_cols(), |r, c| m[(r, c)].clone());
+        m.map_indexed(|r, c, cell| {
+            !rules
+                .highlights(cell, (r, c), &cutoffs, |r, c| m[(r, c)].clone())
+                .is_empty()
+        })
+    }
+    #[test]
+    fn value_conditions() {
+        let m = values();
+        let rules = Rules::new()
+            .with_values(|cell: &Cell<u32>| f64::from(cell.value))
+            .with(Condition::Above(7.0), Highlight::background(1))
+            .with(Condition::Between(1.0, 2.0), Highlight::background(2));
+        assert_eq!(
+            Matrix::from([
+                [true, true, true],
+                [false, false, false],
+                [false, true, true],
+                [false, false, false]
+            ]),
+            matching(&rules, &m)
+        );
+    }
+    #[test]
+    fn rank_conditions() {
+        let m = values();
+        let top = Rules::new()
+            .with_values(|cell: &Cell<u32>| f64::from(cell.value))
+            .with(Condition::Top(0.25), Highlight::background(1));
+        assert_eq!(
+            Matrix::from([
+                [false, false, false],
+                [false, false, false],
+                [true, true, true],
+                [false, false, false]
+            ]),
+            matching(&top, &m)
+        );
+        let bottom = Rules::new()
+            .with_values(|cell: &Cell<u32>| f64::from(cell.value))
+            .with(Condition::Bottom(0.1), Highlight::background(1));
+        assert_eq!(
+            Matrix::from([
+                [true, false, false],
+                [false, false, false],
+                [false, false, false],
+                [true, false, false]
+            ]),
+            matching(&bottom, &m)
+        );
+    }
+    #[test]
+    fn position_and_neighbour_conditions() {
+        let m = values();
+        let rules = Rules::new().with(Condition::OddRow, Highlight::background(1));
+        assert_eq!(
+            vec![false, true, false, true],
+            matching(&rules, &m).col(0).cloned().collect::<Vec<_>>()
+        );
+        let rules = Rules::new().with(Condition::SameAsLeft, Highlight::background(1));
+        assert_eq!(
+            Matrix::from([
+                [false, false, true],
+                [false, false, false],
+                [false, false, false],
+                [false, false, true]
+            ]),
+            matching(&rules, &m)
+        );
+        // Without values, value conditions never match
+        let rules = Rules::new().with(Condition::Below(100.0), Highlight::background(1));
+        assert!(matching(&rules, &m).iter().all(|&x| !x));
+    }
+    #[test]
+    fn later_rules_take_precedence() {
+        let bold = Attributes {
+            bold: true,
+            ..Attributes::default()
+        };
+        let rules = Rules::new()
+            .with_values(|cell: &Cell<u32>| f64::from(cell.value))
+            .with(
+                Condition::Above(0.0),
+                Highlight::background(1).with_attributes(bold),
+            )
+            .with(Condition::Above(1.0), Highlight::background(2))
+            .with(Condition::Above(2.0), Highlight::foreground(3));
+        let f = Format::new(1, 1);
+        let mut m = Matrix::from([[Cell::new(1, 7, 0), Cell::new(3, 7, 0), Cell::new(0, 7, 4)]]);
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.set_rules(rules);
+        let mut s = VirtualScreen::new();
+        d.print(&mut s, &BordersStyle::None);
+        assert_eq!(vec!["130"], s.lines());
+        assert_eq!(
+            (Some(Fixed(7)), Some(Fixed(1))),
+            (s.fg((1, 1)), s.bg((1, 1)))
+        );
+        assert_eq!(
+            (Some(Fixed(3)), Some(Fixed(2))),
+            (s.fg((2, 1)), s.bg((2, 1)))
+        );
+        assert!(s.cell((2, 1)).style.is_bold);
+        assert_eq!(
+            (Some(Fixed(7)), Some(Fixed(4))),
+            (s.fg((3, 1)), s.bg((3, 1)))
+        );
+        assert!(!s.cell((3, 1)).style.is_bold);
+        let mut color = AnsiColor::default();
+        Highlight::background(5).recolor(&mut color);
+        assert_eq!(AnsiColor { fg: 7, bg: 5 }, color);
+    }
+}
+
+/// What a cell must meet for a rule to apply
+///
+/// Rows and columns are counted from 0, so the first row is even.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Condition {
+    /// The value is greater than a threshold
+    Above(f64),
+    /// The value is smaller than a threshold
+    Below(f64),
+    /// The value is between two bounds, included
+    Between(f64, f64),
+    /// The value is among the given fraction of highest values: 0.1 for the top 10%
+    Top(f64),
+    /// The value is among the given fraction of lowest values
+    Bottom(f64),
+    /// The cell is on an even row
+    EvenRow,
+    /// The cell is on an odd row
+    OddRow,
+    /// The cell is on an even column
+    EvenCol,
+    /// The cell is on an odd column
+    OddCol,
+    /// The cell has the same text as the cell on its left
+    SameAsLeft,
+    /// The cell has the same text as the cell above it
+    SameAsAbove,
+}
+
+/// A change to the look of a cell: colors to replace and attributes to add
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Highlight {
+    pub fg: Option<u8>,
+    pub bg: Option<u8>,
+    pub attributes: Attributes,
+}
+impl Highlight {
+    /// A highlight replacing the foreground color
+    pub fn foreground(fg: u8) -> Highlight {
+        Highlight {
+            fg: Some(fg),
+            ..Highlight::default()
+        }
+    }
+    /// A highlight replacing the background color
+    pub fn background(bg: u8) -> Highlight {
+        Highlight {
+            bg: Some(bg),
+            ..Highlight::default()
+        }
+    }
+    /// A highlight adding text attributes
+    pub fn attributes(attributes: Attributes) -> Highlight {
+        Highlight {
+            attributes,
+            ..Highlight::default()
+        }
+    }
+    /// Also add text attributes
+    pub fn with_attributes(self, attributes: Attributes) -> Highlight {
+        Highlight { attributes, ..self }
+    }
+    /// Replace the colors this highlight sets
+    pub fn recolor(&self, color: &mut AnsiColor) {
+        if let Some(fg) = self.fg {
+            color.fg = fg;
+        }
+        if let Some(bg) = self.bg {
+            color.bg = bg;
+        }
+    }
+}
+
+/// The value of a cell, for value conditions
+type Value<'a, C> = Box<dyn Fn(&C) -> f64 + 'a>;
+
+/// Conditional formatting rules for the cells of a `MatrixDisplay`
+///
+/// Value conditions need to know the value of a cell, given by `with_values`: without it, they never match.
+///
+/// # Example: highlighting high scores
+/// ```
+/// use matrix_display::*;
+/// use matrix_display::cell::Attributes;
+/// use matrix_display::rules::{Condition, Highlight, Rules};
+/// let format = Format::new(5, 1);
+/// let mut scores = matrix::Matrix::from([[12, 85, 40], [97, 3, 61]]).map(|&x| cell::Cell::new(x, 7, 0));
+/// let bold = Attributes { bold: true, ..Attributes::default() };
+/// let mut display = MatrixDisplay::new(&format, &mut scores);
+/// display.set_rules(
+///     Rules::new()
+///         .with_values(|cell: &cell::Cell<i32>| f64::from(cell.value))
+///         .with(Condition::Above(50.0), Highlight::background(22))
+///         .with(Condition::Top(0.2), Highlight::attributes(bold)),
+/// );
+/// display.print(&mut std::io::stdout(), &style::BordersStyle::Light);
+/// ```
+pub struct Rules<'a, C> {
+    value: Option<Value<'a, C>>,
+    rules: Vec<(Condition, Highlight)>,
+}
+impl<'a, C> Rules<'a, C>
+where
+    C: RenderCell,
+{
+    /// Construct an empty list of rules
+    pub fn new() -> Rules<'a, C> {
+        Rules {
+            value: None,
+            rules: Vec::new(),
+        }
+    }
+    /// Give value conditions the value of each cell
+    pub fn with_values<F>(self, value: F) -> Rules<'a, C>
+    where
+        F: Fn(&C) -> f64 + 'a,
+    {
+        Rules {
+            value: Some(Box::new(value)),
+            ..self
+        }
+    }
+    /// Add a rule, taking precedence over the previous ones
+    pub fn with(mut self, condition: Condition, highlight: Highlight) -> Rules<'a, C> {
+        self.rules.push((condition, highlight));
+        self
+    }
+    /// The lowest value of the top fraction, or the highest value of the bottom fraction, for each rule
+    ///
+    /// Only rank conditions need every value of the matrix, so `cell(row, col)` is only called for them.
+    pub(crate) fn cutoffs<F>(&self, n_rows: usize, n_cols: usize, cell: F) -> Vec<Option<f64>>
+    where
+        F: Fn(usize, usize) -> C,
+    {
+        let value = match self.value {
+            Some(ref value) => value,
+            None => return vec![None; self.rules.len()],
+        };
+        let needs_ranks = self
+            .rules
+            .iter()
+            .any(|(condition, _)| matches!(condition, Condition::Top(_) | Condition::Bottom(_)));
+        let mut sorted = Vec::new();
+        if needs_ranks {
+            for r in 0..n_rows {
+                for c in 0..n_cols {
+                    let x = value(&cell(r, c));
+                    if !x.is_nan() {
+                        sorted.push(x);
+                    }
+                }
+            }
+            sorted.sort_by(|a, b| a.partial_cmp(b).expect("NaN values are filtered out"));
+        }
+        let count = |fraction: f64| (fraction * sorted.len() as f64).ceil() as usize;
+        self.rules
+            .iter()
+            .map(|(condition, _)| match *condition {
+                Condition::Top(fraction) if count(fraction) > 0 => {
+                    Some(sorted[sorted.len() - std::cmp::min(count(fraction), sorted.len())])
+                }
+                Condition::Bottom(fraction) if count(fraction) > 0 => {
+                    Some(sorted[std::cmp::min(count(fraction), sorted.len()) - 1])
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    }
+    /// The highlights of the rules a cell meets, in order of precedence
+    ///
+    /// `neighbour(row, col)` gives the other cells, for the conditions comparing a cell to them.
+    pub(crate) fn highlights<F>(
+        &self,
+        cell: &C,
+        (row, col): (usize, usize),
+        cutoffs: &[Option<f64>],
+        neighbour: F,
+    ) -> Vec<&Highlight>
+    where
+        F: Fn(usize, usize) -> C,
+    {
+        let x = self
+            .value
+            .as_ref()
+            .map(|value| value(cell))
+            .unwrap_or(f64::NAN);
+        self.rules
+            .iter()
+            .zip(cutoffs)
+            .filter(|((condition, _), cutoff)| match *condition {
+                Condition::Above(threshold) => x > threshold,
+                Condition::Below(threshold) => x < threshold,
+                Condition::Between(low, high) => low <= x && x <= high,
+                Condition::Top(_) => cutoff.is_some_and(|cutoff| x >= cutoff),
+                Condition::Bottom(_) => cutoff.is_some_and(|cutoff| x <= cutoff),
+                Condition::EvenRow => row % 2 == 0,
+                Condition::OddRow => row % 2 == 1,
+                Condition::EvenCol => col % 2 == 0,
+                Condition::OddCol => col % 2 == 1,
+                Condition::SameAsLeft => col > 0 && neighbour(row, col - 1).text() == cell.text(),
+                Condition::SameAsAbove => row > 0 && neighbour(row - 1, col).text() == cell.text(),
+            })
+            .map(|((_, highlight), _)| highlight)
+            .collect::<Vec<_>>()
+    }
+}
+impl<'a, C> Default for Rules<'a, C>
+where
+    C: RenderCell,
+{
+    fn default() -> Rules<'a, C> {
+        Rules::new()
+    }
+}
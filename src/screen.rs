@@ -0,0 +1,448 @@
+//! An in-memory terminal to check what a rendered matrix looks like on screen
+//!
+//! `VirtualScreen` consumes the output of `MatrixDisplay::render` (or anything else written to it)
+//! and interprets it the way a terminal would: printable characters, SGR color and attribute escapes,
+//! cursor movements, carriage returns and line feeds.
+//! It then exposes the character and the style found at each position of the screen, so tests can assert
+//! on what the user actually sees rather than on the byte stream.
+//!
+//! Positions are terminal cursor positions (x, y) in characters, starting at (1, 1) in the top left corner,
+//! just like the cursor positions accepted by `MatrixDisplay::cell_at_cursor_position`.
+
+extern crate unicode_width;
+
+use ansi_term::Colour;
+use ansi_term::Style;
+use std::io::Write;
+use unicode_width::UnicodeWidthChar;
+
+#[cfg(test)]
+mod virtual_screen_tests {
+    use super::{ScreenCell, VirtualScreen};
+    use crate::cell::Cell;
+    use crate::matrix::Matrix;
+    use crate::redraw::Redraw;
+    use crate::style::BordersStyle;
+    use crate::{Format, MatrixDisplay};
+    use ansi_term::Colour::{Fixed, Red, RGB};
+    use ansi_term::Style;
+    #[test]
+    fn plain_text() {
+        let mut s = VirtualScreen::new();
+        s.feed("ab\r\ncd");
+        assert_eq!('a', s.cell((1, 1)).ch);
+        assert_eq!('d', s.cell((2, 2)).ch);
+        assert_eq!(ScreenCell::default(), s.cell((5, 5)));
+        assert_eq!((3, 2), s.cursor());
+        assert_eq!(vec!["ab", "cd"], s.lines());
+    }
+    #[test]
+    fn colors_and_attributes() {
+        let mut s = VirtualScreen::new();
+        s.feed("\x1b[1;4;48;5;10;38;5;3mx\x1b[0my\x1b[31;48;2;1;2;3mz");
+        let x = s.cell((1, 1)).style;
+        assert!(x.is_bold && x.is_underline && !x.is_italic);
+        assert_eq!(Some(Fixed(10)), s.bg((1, 1)));
+        assert_eq!(Some(Fixed(3)), s.fg((1, 1)));
+        assert_eq!(Style::default(), s.cell((2, 1)).style);
+        assert_eq!(Some(Red), s.fg((3, 1)));
+        assert_eq!(Some(RGB(1, 2, 3)), s.bg((3, 1)));
+    }
+    #[test]
+    fn cursor_movements() {
+        let mut s = VirtualScreen::new();
+        s.feed("\x1b[3;4Ha\x1b[2Db\x1b[Ac\x1b[2Bd\x1b[Ce\x1b[H");
+        assert_eq!("   c", s.line(2));
+        assert_eq!("  ba", s.line(3));
+        assert_eq!("    d e", s.line(4));
+        assert_eq!((1, 1), s.cursor());
+    }
+    #[test]
+    fn wide_characters() {
+        let mut s = VirtualScreen::new();
+        s.feed("中x");
+        assert_eq!('中', s.cell((1, 1)).ch);
+        assert_eq!('x', s.cell((3, 1)).ch);
+        assert_eq!("中x", s.line(1));
+    }
+    #[test]
+    fn escapes_split_across_writes() {
+        use std::io::Write;
+        let mut s = VirtualScreen::new();
+        let bytes = "\x1b[48;5;9m中".as_bytes();
+        for b in bytes {
+            s.write_all(&[*b]).unwrap();
+        }
+        assert_eq!('中', s.cell((1, 1)).ch);
+        assert_eq!(Some(Fixed(9)), s.bg((1, 1)));
+    }
+    #[test]
+    fn chess_board() {
+        let f = Format::new(7, 3);
+        let board = (0..64)
+            .map(|i| {
+                let bg = if i % 2 + (i / 8) % 2 == 1 { 7 } else { 0 };
+                Cell::new('♟', 33, bg)
+            })
+            .collect::<Vec<_>>();
+        let mut m = Matrix::new(8, board);
+        let mut d = MatrixDisplay::new(&f, &mut m);
+        d.cell_at_cursor_position((13, 6)).color.bg = 10;
+        let mut s = VirtualScreen::new();
+        d.print(&mut s, &BordersStyle::None);
+        assert_eq!(Some(Fixed(10)), s.bg((13, 6)));
+        assert_eq!(Some(Fixed(10)), s.bg((8, 4)));
+        assert_eq!(Some(Fixed(7)), s.bg((7, 6)));
+        assert_eq!(Some(Fixed(0)), s.bg((1, 1)));
+        assert_eq!('♟', s.cell((11, 5)).ch);
+        assert_eq!(Some(Fixed(33)), s.fg((11, 5)));
+        assert_eq!(24, s.lines().len());
+    }
+    #[test]
+    fn redraw_replays_to_same_screen_as_full_render() {
+        let f = Format::new(3, 3);
+        let mut m = Matrix::new(3, (0..9).map(|x| Cell::new(x, 7, 0)).collect::<Vec<_>>());
+        let mut redraw = Redraw::new();
+        let mut replayed = VirtualScreen::new();
+        redraw.print(
+            &MatrixDisplay::new(&f, &mut m),
+            &mut replayed,
+            &BordersStyle::Heavy,
+        );
+        m.at((2, 0)).value = 42;
+        m.at((0, 2)).color.bg = 12;
+        redraw.print(
+            &MatrixDisplay::new(&f, &mut m),
+            &mut replayed,
+            &BordersStyle::Heavy,
+        );
+        let mut expected = VirtualScreen::new();
+        MatrixDisplay::new(&f, &mut m).print(&mut expected, &BordersStyle::Heavy);
+        assert_eq!(expected.lines(), replayed.lines());
+        for y in 1..=expected.lines().len() {
+            for x in 1..=13 {
+                assert_eq!(expected.cell((x, y)), replayed.cell((x, y)));
+            }
+        }
+    }
+}
+
+/// Matrices and helpers shared by the tests of several modules
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::VirtualScreen;
+    use crate::cell::Cell;
+    use crate::matrix::Matrix;
+    use crate::style::BordersStyle;
+    use crate::MatrixDisplay;
+    pub(crate) fn letters() -> Matrix<Cell<char>> {
+        // a b c d
+        // e f g h
+        // i j k l
+        Matrix::new(
+            4,
+            "abcdefghijkl"
+                .chars()
+                .map(|c| Cell::new(c, 7, 0))
+                .collect::<Vec<_>>(),
+        )
+    }
+    /// The text of the lines printed by a display
+    pub(crate) fn screen(d: &MatrixDisplay<char>, borders: &BordersStyle) -> Vec<String> {
+        let mut s = VirtualScreen::new();
+        d.print(&mut s, borders);
+        s.lines()
+    }
+}
+
+/// What a single position of the screen holds: a character and the style it was printed with
+///
+/// The position right after a double width character holds '\0'.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScreenCell {
+    pub ch: char,
+    pub style: Style,
+}
+impl Default for ScreenCell {
+    fn default() -> ScreenCell {
+        ScreenCell {
+            ch: ' ',
+            style: Style::default(),
+        }
+    }
+}
+
+/// Where the parser stands within the stream of characters
+enum State {
+    Ground,
+    Escape,
+    Csi(String),
+}
+
+/// A grid of characters and styles built by interpreting terminal output
+///
+/// #Example:
+/// ```
+/// use matrix_display::*;
+/// let format = Format::new(3, 1);
+/// let mut data = matrix::Matrix::new(2, (0..4).map(|x| cell::Cell::new(x, 7, x as u8)).collect::<Vec<_>>());
+/// let display = MatrixDisplay::new(&format, &mut data);
+/// let mut screen = screen::VirtualScreen::new();
+/// display.print(&mut screen, &style::BordersStyle::None);
+/// assert_eq!('3', screen.cell((5, 2)).ch);
+/// assert_eq!(Some(ansi_term::Colour::Fixed(3)), screen.bg((5, 2)));
+/// ```
+pub struct VirtualScreen {
+    rows: Vec<Vec<ScreenCell>>,
+    cursor: (usize, usize),
+    style: Style,
+    state: State,
+    pending: Vec<u8>,
+}
+impl VirtualScreen {
+    /// Construct an empty screen with the cursor in the top left corner
+    pub fn new() -> VirtualScreen {
+        VirtualScreen {
+            rows: Vec::new(),
+            cursor: (0, 0),
+            style: Style::default(),
+            state: State::Ground,
+            pending: Vec::new(),
+        }
+    }
+    /// Interpret a piece of terminal output
+    pub fn feed(&mut self, s: &str) {
+        for c in s.chars() {
+            self.feed_char(c);
+        }
+    }
+    /// The character and style at the given position (x, y)
+    ///
+    /// Positions that were never written to hold a blank with the default style.
+    pub fn cell(&self, (x, y): (usize, usize)) -> ScreenCell {
+        self.rows
+            .get(y.wrapping_sub(1))
+            .and_then(|row| row.get(x.wrapping_sub(1)))
+            .cloned()
+            .unwrap_or_default()
+    }
+    /// The foreground color at the given position (x, y)
+    pub fn fg(&self, pos: (usize, usize)) -> Option<Colour> {
+        self.cell(pos).style.foreground
+    }
+    /// The background color at the given position (x, y)
+    pub fn bg(&self, pos: (usize, usize)) -> Option<Colour> {
+        self.cell(pos).style.background
+    }
+    /// Number of columns written to so far
+    pub fn width(&self) -> usize {
+        self.rows.iter().map(|row| row.len()).max().unwrap_or(0)
+    }
+    /// Number of lines written to so far
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+    /// The current cursor position (x, y)
+    pub fn cursor(&self) -> (usize, usize) {
+        (self.cursor.0 + 1, self.cursor.1 + 1)
+    }
+    /// The text of line y, without styles nor trailing blanks
+    pub fn line(&self, y: usize) -> String {
+        self.rows
+            .get(y.wrapping_sub(1))
+            .map(|row| {
+                row.iter()
+                    .map(|cell| cell.ch)
+                    .filter(|&ch| ch != '\0')
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .unwrap_or_default()
+    }
+    /// The text of every line written to so far, without styles nor trailing blanks
+    pub fn lines(&self) -> Vec<String> {
+        (1..=self.rows.len()).map(|y| self.line(y)).collect()
+    }
+    fn feed_char(&mut self, c: char) {
+        let state = std::mem::replace(&mut self.state, State::Ground);
+        self.state = match state {
+            State::Ground => match c {
+                '\x1b' => State::Escape,
+                '\r' => {
+                    self.cursor.0 = 0;
+                    State::Ground
+                }
+                '\n' => {
+                    self.cursor.1 += 1;
+                    State::Ground
+                }
+                '\x08' => {
+                    self.cursor.0 = self.cursor.0.saturating_sub(1);
+                    State::Ground
+                }
+                '\t' => {
+                    self.cursor.0 = (self.cursor.0 / 8 + 1) * 8;
+                    State::Ground
+                }
+                c if c.is_control() => State::Ground,
+                c => {
+                    self.put(c);
+                    State::Ground
+                }
+            },
+            State::Escape => match c {
+                '[' => State::Csi(String::new()),
+                _ => State::Ground,
+            },
+            State::Csi(mut params) => match c {
+                '\x40'..='\x7e' => {
+                    self.csi(&params, c);
+                    State::Ground
+                }
+                _ => {
+                    params.push(c);
+                    State::Csi(params)
+                }
+            },
+        };
+    }
+    fn put(&mut self, c: char) {
+        let width = c.width().unwrap_or(0);
+        if width == 0 {
+            return;
+        }
+        let (x, y) = self.cursor;
+        if self.rows.len() <= y {
+            self.rows.resize(y + 1, Vec::new());
+        }
+        let row = &mut self.rows[y];
+        if row.len() < x + width {
+            row.resize(x + width, ScreenCell::default());
+        }
+        row[x] = ScreenCell {
+            ch: c,
+            style: self.style,
+        };
+        for cell in row.iter_mut().skip(x + 1).take(width - 1) {
+            *cell = ScreenCell {
+                ch: '\0',
+                style: self.style,
+            };
+        }
+        self.cursor.0 += width;
+    }
+    fn csi(&mut self, params: &str, command: char) {
+        let numbers = params
+            .split(';')
+            .map(|p| p.parse::<usize>().unwrap_or(0))
+            .collect::<Vec<_>>();
+        let first_or_one = std::cmp::max(numbers[0], 1);
+        match command {
+            'm' => self.sgr(&numbers),
+            'H' | 'f' => {
+                let y = numbers.first().cloned().unwrap_or(1);
+                let x = numbers.get(1).cloned().unwrap_or(1);
+                self.cursor = (x.saturating_sub(1), y.saturating_sub(1));
+            }
+            'A' => self.cursor.1 = self.cursor.1.saturating_sub(first_or_one),
+            'B' => self.cursor.1 += first_or_one,
+            'C' => self.cursor.0 += first_or_one,
+            'D' => self.cursor.0 = self.cursor.0.saturating_sub(first_or_one),
+            'G' => self.cursor.0 = first_or_one - 1,
+            'J' if numbers[0] == 2 => self.rows.clear(),
+            'K' => {
+                if let Some(row) = self.rows.get_mut(self.cursor.1) {
+                    row.truncate(self.cursor.0);
+                }
+            }
+            _ => {}
+        }
+    }
+    fn sgr(&mut self, numbers: &[usize]) {
+        let mut codes = numbers.iter().cloned();
+        while let Some(code) = codes.next() {
+            match code {
+                0 => self.style = Style::default(),
+                1 => self.style.is_bold = true,
+                2 => self.style.is_dimmed = true,
+                3 => self.style.is_italic = true,
+                4 => self.style.is_underline = true,
+                5 => self.style.is_blink = true,
+                7 => self.style.is_reverse = true,
+                8 => self.style.is_hidden = true,
+                9 => self.style.is_strikethrough = true,
+                22 => {
+                    self.style.is_bold = false;
+                    self.style.is_dimmed = false;
+                }
+                23 => self.style.is_italic = false,
+                24 => self.style.is_underline = false,
+                25 => self.style.is_blink = false,
+                27 => self.style.is_reverse = false,
+                28 => self.style.is_hidden = false,
+                29 => self.style.is_strikethrough = false,
+                30..=37 => self.style.foreground = Some(basic_colour(code - 30)),
+                38 => self.style.foreground = extended_colour(&mut codes),
+                39 => self.style.foreground = None,
+                40..=47 => self.style.background = Some(basic_colour(code - 40)),
+                48 => self.style.background = extended_colour(&mut codes),
+                49 => self.style.background = None,
+                90..=97 => self.style.foreground = Some(Colour::Fixed((code - 90 + 8) as u8)),
+                100..=107 => self.style.background = Some(Colour::Fixed((code - 100 + 8) as u8)),
+                _ => {}
+            }
+        }
+    }
+}
+impl Default for VirtualScreen {
+    fn default() -> VirtualScreen {
+        VirtualScreen::new()
+    }
+}
+impl Write for VirtualScreen {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        let valid = match std::str::from_utf8(&self.pending) {
+            Ok(s) => s.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(e) => {
+                return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, e));
+            }
+        };
+        let text = String::from_utf8(self.pending.drain(..valid).collect()).unwrap();
+        self.feed(&text);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// One of the 8 colours from the original terminal palette, by index
+fn basic_colour(index: usize) -> Colour {
+    match index {
+        0 => Colour::Black,
+        1 => Colour::Red,
+        2 => Colour::Green,
+        3 => Colour::Yellow,
+        4 => Colour::Blue,
+        5 => Colour::Purple,
+        6 => Colour::Cyan,
+        _ => Colour::White,
+    }
+}
+
+/// A 256 colors ("5;n") or true color ("2;r;g;b") colour, following a 38 or 48 SGR code
+fn extended_colour<I: Iterator<Item = usize>>(codes: &mut I) -> Option<Colour> {
+    match codes.next() {
+        Some(5) => codes.next().map(|n| Colour::Fixed(n as u8)),
+        Some(2) => {
+            let r = codes.next().unwrap_or(0) as u8;
+            let g = codes.next().unwrap_or(0) as u8;
+            let b = codes.next().unwrap_or(0) as u8;
+            Some(Colour::RGB(r, g, b))
+        }
+        _ => None,
+    }
+}
@@ -9,7 +9,8 @@ use unicode_types::box_drawing::BoxDrawing;
 /// An enum that represents different unicode borders styles
 ///
 /// It provides accessors for specific locations such as top_left_corner to be used by the printing code
-#[derive(PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BordersStyle {
     /// ✓✓
     /// ✓✓
@@ -53,6 +54,13 @@ pub enum BordersStyle {
 }
 
 impl BordersStyle {
+    /// A style that stands out from this one, used to separate frozen cells from scrolling ones
+    pub fn separator(&self) -> BordersStyle {
+        match *self {
+            BordersStyle::Heavy => BordersStyle::Double,
+            _ => BordersStyle::Heavy,
+        }
+    }
     pub fn top_left_corner(&self) -> char {
         match *self {
             BordersStyle::None => '\0',
@@ -0,0 +1,112 @@
+//! Query the size of the terminal and get notified when it changes
+//!
+//! The size is read from the terminal driver (`TIOCGWINSZ`) on unix,
+//! falling back to the `COLUMNS` and `LINES` environment variables.
+//! Combine it with `Format::fit` or `MatrixDisplay::fit_viewport` to make a matrix fill the terminal,
+//! and with `watch_resize` and `was_resized` to lay it out again whenever the terminal gets resized.
+//!
+//! # Example: fitting a board to the terminal in a loop
+//! ```no_run
+//! use matrix_display::*;
+//! let borders = style::BordersStyle::Light;
+//! let mut data = matrix::Matrix::new(4, (0..16).map(|x| cell::Cell::new(x, 7, 0)).collect::<Vec<_>>());
+//! let mut redraw = redraw::Redraw::new();
+//! terminal::watch_resize();
+//! let (n_rows, n_cols) = (data.n_rows(), data.n_cols());
+//! let fit = |size| Format::fit(n_rows, n_cols, &borders, size);
+//! let mut format = fit(terminal::size().unwrap_or((80, 24)));
+//! loop {
+//!     if terminal::was_resized() {
+//!         format = fit(terminal::size().unwrap_or((80, 24)));
+//!         redraw.invalidate();
+//!     }
+//!     redraw.print(&MatrixDisplay::new(&format, &mut data), &mut std::io::stdout(), &borders);
+//!     std::thread::sleep(std::time::Duration::from_millis(100));
+//! }
+//! ```
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(test)]
+mod terminal_tests {
+    use super::size_from_env;
+    #[test]
+    fn size_from_environment() {
+        let s = |x: &str| Some(x.to_string());
+        assert_eq!(Some((80, 24)), size_from_env(s("80"), s("24")));
+        assert_eq!(None, size_from_env(s("80"), None));
+        assert_eq!(None, size_from_env(s("wide"), s("24")));
+        assert_eq!(None, size_from_env(s("0"), s("24")));
+    }
+    #[cfg(unix)]
+    #[test]
+    fn resize_notification() {
+        super::watch_resize();
+        super::was_resized();
+        unsafe {
+            libc::raise(libc::SIGWINCH);
+        }
+        assert!(super::was_resized());
+        assert!(!super::was_resized());
+    }
+}
+
+static RESIZED: AtomicBool = AtomicBool::new(false);
+
+/// The size of the terminal in characters (width, height), if it can be found
+pub fn size() -> Option<(usize, usize)> {
+    size_from_driver()
+        .or_else(|| size_from_env(std::env::var("COLUMNS").ok(), std::env::var("LINES").ok()))
+}
+
+#[cfg(unix)]
+fn size_from_driver() -> Option<(usize, usize)> {
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    let found = [libc::STDOUT_FILENO, libc::STDIN_FILENO, libc::STDERR_FILENO]
+        .iter()
+        .any(|&fd| unsafe { libc::ioctl(fd, libc::TIOCGWINSZ, &mut size) } == 0);
+    if found && size.ws_col > 0 && size.ws_row > 0 {
+        Some((size.ws_col as usize, size.ws_row as usize))
+    } else {
+        None
+    }
+}
+
+#[cfg(not(unix))]
+fn size_from_driver() -> Option<(usize, usize)> {
+    None
+}
+
+fn size_from_env(columns: Option<String>, lines: Option<String>) -> Option<(usize, usize)> {
+    let parse = |x: Option<String>| x.and_then(|x| x.trim().parse::<usize>().ok());
+    match (parse(columns), parse(lines)) {
+        (Some(w), Some(h)) if w > 0 && h > 0 => Some((w, h)),
+        _ => None,
+    }
+}
+
+/// Start listening to terminal resizes (`SIGWINCH`)
+///
+/// This replaces any previous `SIGWINCH` handler. It does nothing on platforms without that signal.
+/// Reads and writes interrupted by a resize are restarted rather than failing with `EINTR`.
+pub fn watch_resize() {
+    #[cfg(unix)]
+    unsafe {
+        let handler: extern "C" fn(libc::c_int) = on_resize;
+        let mut action: libc::sigaction = std::mem::zeroed();
+        action.sa_sigaction = handler as libc::sighandler_t;
+        action.sa_flags = libc::SA_RESTART;
+        libc::sigemptyset(&mut action.sa_mask);
+        libc::sigaction(libc::SIGWINCH, &action, std::ptr::null_mut());
+    }
+}
+
+#[cfg(unix)]
+extern "C" fn on_resize(_: libc::c_int) {
+    RESIZED.store(true, Ordering::SeqCst);
+}
+
+/// Whether the terminal was resized since the last call, once `watch_resize` was called
+pub fn was_resized() -> bool {
+    RESIZED.swap(false, Ordering::SeqCst)
+}
@@ -0,0 +1,111 @@
+//! Render only a window of a matrix that is too large to be printed whole
+//!
+//! A `Viewport` is made of the row and column of the first visible cell plus the size of the window,
+//! either in cells or in characters.
+//! When the size is given in characters, the cells crossing the right or bottom edges get truncated.
+
+#[cfg(test)]
+mod viewport_tests {
+    use super::{Viewport, ViewportSize};
+    #[test]
+    fn constructors() {
+        let v = Viewport::cells(3, 4);
+        assert_eq!(ViewportSize::Cells { rows: 3, cols: 4 }, v.size);
+        assert_eq!((0, 0), (v.row, v.col));
+        assert!(!v.scroll_indicators);
+        let v = Viewport::chars(80, 24).at(10, 20).with_scroll_indicators();
+        assert_eq!(
+            ViewportSize::Chars {
+                width: 80,
+                height: 24
+            },
+            v.size
+        );
+        assert_eq!((10, 20), (v.row, v.col));
+        assert!(v.scroll_indicators);
+    }
+    #[test]
+    fn scroll() {
+        let mut v = Viewport::cells(3, 4).at(5, 5);
+        v.scroll(2, -3);
+        assert_eq!((7, 2), (v.row, v.col));
+        v.scroll(-10, -10);
+        assert_eq!((0, 0), (v.row, v.col));
+    }
+}
+
+/// The size of the visible window
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ViewportSize {
+    /// A number of rows and columns of cells
+    Cells { rows: usize, cols: usize },
+    /// A width and a height in characters, borders included
+    Chars { width: usize, height: usize },
+}
+
+/// A window onto a matrix: the first visible row and column and the size of the window
+///
+/// When `scroll_indicators` is set, the last line of the window shows arrows pointing
+/// towards the directions where more cells can be scrolled into view.
+///
+/// # Example:
+/// ```
+/// use matrix_display::*;
+/// let format = Format::new(8, 1);
+/// let mut data = matrix::Matrix::new(1000, (0..1_000_000).map(|x| cell::Cell::new(x, 7, 0)).collect::<Vec<_>>());
+/// let mut display = MatrixDisplay::new(&format, &mut data);
+/// display.set_viewport(viewport::Viewport::chars(80, 24).at(500, 500).with_scroll_indicators());
+/// display.print(&mut std::io::stdout(), &style::BordersStyle::Light);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Viewport {
+    pub row: usize,
+    pub col: usize,
+    pub size: ViewportSize,
+    pub scroll_indicators: bool,
+}
+impl Viewport {
+    /// Construct a viewport showing a number of rows and columns of cells, from the top left cell
+    pub fn cells(rows: usize, cols: usize) -> Viewport {
+        Viewport::with_size(ViewportSize::Cells { rows, cols })
+    }
+    /// Construct a viewport fitting in a width and a height in characters, from the top left cell
+    pub fn chars(width: usize, height: usize) -> Viewport {
+        Viewport::with_size(ViewportSize::Chars { width, height })
+    }
+    fn with_size(size: ViewportSize) -> Viewport {
+        Viewport {
+            row: 0,
+            col: 0,
+            size,
+            scroll_indicators: false,
+        }
+    }
+    /// Move the viewport so that its first visible cell is at the given row and column
+    pub fn at(self, row: usize, col: usize) -> Viewport {
+        Viewport { row, col, ..self }
+    }
+    /// Show scroll indicators on the last line of the viewport
+    pub fn with_scroll_indicators(self) -> Viewport {
+        Viewport {
+            scroll_indicators: true,
+            ..self
+        }
+    }
+    /// Scroll by a number of rows and columns, stopping at the top left corner of the matrix
+    ///
+    /// A viewport doesn't know the size of the matrix, so it can be scrolled past its bottom right corner:
+    /// use `MatrixDisplay::scroll` to stop there too.
+    pub fn scroll(&mut self, rows: isize, cols: isize) {
+        self.row = offset(self.row, rows);
+        self.col = offset(self.col, cols);
+    }
+}
+
+fn offset(x: usize, by: isize) -> usize {
+    if by < 0 {
+        x.saturating_sub(by.unsigned_abs())
+    } else {
+        x.saturating_add(by as usize)
+    }
+}